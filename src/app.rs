@@ -1,18 +1,41 @@
 use anyhow::Result;
+use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 use tui::widgets::{ListState, TableState};
 
+use crate::config::Config;
 use crate::file_operations;
+use crate::find_replace::{self, ReplaceState};
+use crate::formats;
+use crate::formats::registry::{Document, Format, FormatContext};
+use crate::formats::tmx;
+use crate::glossary::Glossary;
+use crate::hook;
 use crate::localization;
+use crate::machine;
+use crate::memory::{MemoryMatch, Purge, TranslationMemory};
+use crate::review::{self, ReviewState};
+use crate::spelling::{SpellCache, Speller};
+use crate::tree;
+use crate::ui;
+use crate::validation;
+
+// Quantidade de sugestões da memória exibidas nos detalhes
+const MEMORY_SUGGESTIONS: usize = 3;
 
 #[derive(Clone, PartialEq)]
 pub enum AppState {
     FileSelection,
     Editing,
     SaveConfirmation,
+    // Gerenciamento da memória de tradução, aberto a partir da seleção de arquivos
+    Memory,
     Exiting,
 }
 
@@ -25,19 +48,183 @@ pub enum Language {
 pub struct FileSelectionState {
     pub files: Vec<PathBuf>,
     pub list_state: ListState,
+    // Aviso da abertura (glossário ou dicionário que não pôde ser lido), até a
+    // próxima tecla
+    pub message: Option<String>,
 }
 
 pub struct Entry {
     pub key: String,
     pub original: Value,
+    // `Null` enquanto a entrada não recebeu tradução, o que a diferencia de
+    // uma tradução igual ao texto de origem
     pub translated: Value,
-    pub is_translated: bool,
+    pub status: EntryStatus,
+    pub lock: Lock,
+    // Tamanho máximo da tradução em caracteres, de `length_limits`
+    pub max_length: Option<usize>,
+    pub dirty: bool,
+    pub modified: Option<DateTime<Local>>,
+    // Contexto somente leitura vindo do arquivo (comentários, descrições)
+    pub notes: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EntryStatus {
+    Untranslated,
+    NeedsReview,
+    Translated,
+    // Rascunho da tradução automática, ainda não conta como traduzido
+    Draft,
+}
+
+// Entradas que não devem ser traduzidas (marcas, URLs, códigos): ficam só
+// leitura e são gravadas com o texto de origem. O bloqueio vem do arquivo
+// auxiliar, marcado pelo usuário, ou dos padrões de `locked_keys` na configuração.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Lock {
+    Unlocked,
+    Manual,
+    Config,
+}
+
+impl EntryStatus {
+    pub fn next(self) -> Self {
+        match self {
+            EntryStatus::Untranslated => EntryStatus::NeedsReview,
+            EntryStatus::NeedsReview => EntryStatus::Translated,
+            EntryStatus::Translated => EntryStatus::Untranslated,
+            EntryStatus::Draft => EntryStatus::NeedsReview,
+        }
+    }
 }
 
+impl Entry {
+    pub fn is_translated(&self) -> bool {
+        self.status == EntryStatus::Translated
+    }
+
+    pub fn has_translation(&self) -> bool {
+        !self.translated.is_null()
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.lock != Lock::Unlocked
+    }
+
+    // Ainda precisa de tradução: nem traduzida nem bloqueada
+    pub fn is_pending(&self) -> bool {
+        !self.is_translated() && !self.is_locked()
+    }
+
+    pub fn touch(&mut self) {
+        self.dirty = true;
+        self.modified = Some(Local::now());
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SortMode {
+    Original,
+    Key,
+    Status,
+    Length,
+    Modified,
+}
+
+impl SortMode {
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::Original => SortMode::Key,
+            SortMode::Key => SortMode::Status,
+            SortMode::Status => SortMode::Length,
+            SortMode::Length => SortMode::Modified,
+            SortMode::Modified => SortMode::Original,
+        }
+    }
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            SortMode::Original => "sort_original",
+            SortMode::Key => "sort_key",
+            SortMode::Status => "sort_status",
+            SortMode::Length => "sort_length",
+            SortMode::Modified => "sort_modified",
+        }
+    }
+}
+
+// Filtros fixos da tabela; todos os ativos precisam ser satisfeitos.
+#[derive(Default)]
+pub struct Filter {
+    pub untranslated: bool,
+    pub translated: bool,
+    pub dirty: bool,
+    pub issues: bool,
+    pub empty: bool,
+    pub identical: bool,
+    pub prefix: String,
+}
+
+impl Filter {
+    pub fn is_active(&self) -> bool {
+        self.untranslated
+            || self.translated
+            || self.dirty
+            || self.issues
+            || self.empty
+            || self.identical
+            || !self.prefix.is_empty()
+    }
+
+    pub fn matches(&self, entry: &Entry, glossary: &Glossary) -> bool {
+        let empty = match &entry.translated {
+            Value::String(s) => s.is_empty(),
+            Value::Null => true,
+            _ => false,
+        };
+
+        (!self.untranslated || entry.is_pending())
+            && (!self.translated || entry.is_translated())
+            && (!self.dirty || entry.dirty)
+            && (!self.empty || empty)
+            && (!self.identical || entry.translated == entry.original)
+            && entry.key.starts_with(&self.prefix)
+            && (!self.issues || validation::has_issues(entry, glossary))
+    }
+}
+
+pub enum ViewRow {
+    Entry {
+        index: usize,
+        depth: usize,
+    },
+    Namespace {
+        path: String,
+        depth: usize,
+        translated: usize,
+        total: usize,
+    },
+}
+
+// Estado anterior de uma entrada, usado para desfazer alterações.
+pub struct EntrySnapshot {
+    pub index: usize,
+    pub translated: Value,
+    pub status: EntryStatus,
+}
+
+pub type UndoStep = Vec<EntrySnapshot>;
+
 pub struct EditingState {
     pub entries: Vec<Entry>,
     pub table_state: TableState,
     pub original_path: PathBuf,
+    pub document: Document,
+    // Formato usado para abrir o arquivo, e também para gravá-lo
+    pub format: &'static dyn Format,
+    // Glossário do `App`, usado na validação e no filtro de problemas
+    pub glossary: Rc<Glossary>,
     pub editing: Option<usize>,
     pub input: String,
     pub cursor_pos: usize,
@@ -48,6 +235,461 @@ pub struct EditingState {
     pub total_keys: usize,
     pub translated_keys: isize,
     pub save_notification: Option<Instant>,
+    // Texto da notificação, quando não é a confirmação padrão de salvamento
+    pub notification_message: Option<String>,
+    pub replace: Option<ReplaceState>,
+    // Prévia de alterações importadas, aguardando confirmação
+    pub review: Option<ReviewState>,
+    pub undo_stack: Vec<UndoStep>,
+    pub filter: Filter,
+    pub filter_mode: bool,
+    pub filter_prefix_input: bool,
+    // Índices das entradas que passam pelo filtro, na ordem exibida
+    pub view: Vec<usize>,
+    // Linhas da tabela; no modo árvore incluem os namespaces e omitem os recolhidos
+    pub rows: Vec<ViewRow>,
+    pub tree_mode: bool,
+    pub collapsed: HashSet<String>,
+    // Entradas marcadas para ações em lote
+    pub marked: BTreeSet<usize>,
+    // Quantidade de linhas visíveis, atualizada a cada renderização
+    pub page_size: usize,
+    pub goto_mode: bool,
+    pub goto_input: String,
+    pub auto_advance: bool,
+    pub sort_mode: SortMode,
+    // Sugestões da memória de tradução para a entrada em `memory_for`
+    pub memory_matches: Vec<MemoryMatch>,
+    pub memory_for: Option<usize>,
+    // Tradução automática que preencheu a edição em andamento
+    pub machine_input: Option<String>,
+    // Palavras desconhecidas e sugestões já calculadas para a tela
+    pub spelling: SpellCache,
+}
+
+impl EditingState {
+    pub fn new(
+        original_path: PathBuf,
+        entries: Vec<Entry>,
+        document: Document,
+        format: &'static dyn Format,
+        glossary: Rc<Glossary>,
+    ) -> Self {
+        let mut state = Self {
+            total_keys: 0,
+            entries,
+            table_state: TableState::default(),
+            original_path,
+            document,
+            format,
+            glossary,
+            editing: None,
+            input: String::new(),
+            cursor_pos: 0,
+            search_query: String::new(),
+            search_mode: false,
+            search_results: Vec::new(),
+            search_selection: None,
+            translated_keys: 0,
+            save_notification: None,
+            notification_message: None,
+            replace: None,
+            review: None,
+            undo_stack: Vec::new(),
+            filter: Filter::default(),
+            filter_mode: false,
+            filter_prefix_input: false,
+            view: Vec::new(),
+            rows: Vec::new(),
+            tree_mode: false,
+            collapsed: HashSet::new(),
+            marked: BTreeSet::new(),
+            page_size: 0,
+            goto_mode: false,
+            goto_input: String::new(),
+            auto_advance: false,
+            sort_mode: SortMode::Original,
+            memory_matches: Vec::new(),
+            memory_for: None,
+            machine_input: None,
+            spelling: SpellCache::default(),
+        };
+        state.recount_translated();
+        state.refresh_view();
+        state
+    }
+
+    pub fn snapshot(&self, index: usize) -> EntrySnapshot {
+        let entry = &self.entries[index];
+        EntrySnapshot {
+            index,
+            translated: entry.translated.clone(),
+            status: entry.status,
+        }
+    }
+
+    pub fn notify(&mut self, message: String) {
+        self.notification_message = Some(message);
+        self.save_notification = Some(Instant::now());
+    }
+
+    // Aplica tradução e estado recebidos de fora. Retorna o estado anterior
+    // quando a entrada mudou, para compor o passo de desfazer.
+    pub fn apply_change(
+        &mut self,
+        index: usize,
+        translated: Option<Value>,
+        status: Option<EntryStatus>,
+    ) -> Option<EntrySnapshot> {
+        if self.entries[index].is_locked() {
+            return None;
+        }
+        let snapshot = self.snapshot(index);
+        let entry = &mut self.entries[index];
+        if let Some(translated) = translated {
+            entry.translated = translated;
+        }
+        if let Some(status) = status {
+            entry.status = status;
+        }
+        if entry.translated != snapshot.translated {
+            entry.touch();
+        } else if entry.status != snapshot.status {
+            entry.modified = Some(Local::now());
+        } else {
+            return None;
+        }
+        Some(snapshot)
+    }
+
+    // Abre a edição com a tradução como está, com quebras de linha e aspas
+    pub fn start_editing(&mut self, index: usize) {
+        self.editing = Some(index);
+        self.input = formats::text_of(&self.entries[index].translated);
+        self.cursor_pos = self.input.chars().count();
+    }
+
+    // Grava o texto da edição na entrada, com desfazer
+    pub fn apply_input(&mut self, index: usize) {
+        let snapshot = self.snapshot(index);
+        self.push_undo(vec![snapshot]);
+        let entry = &mut self.entries[index];
+        // Confirmar a edição vazia não cria uma tradução vazia
+        let value = if self.input.is_empty() && !entry.has_translation() {
+            Value::Null
+        } else {
+            Value::String(self.input.clone())
+        };
+        if entry.translated != value {
+            entry.translated = value;
+            entry.touch();
+        }
+    }
+
+    pub fn push_undo(&mut self, step: UndoStep) {
+        if !step.is_empty() {
+            self.undo_stack.push(step);
+        }
+    }
+
+    // Progresso sem as entradas bloqueadas, que não são traduzidas
+    pub fn recount_translated(&mut self) {
+        let unlocked = self.entries.iter().filter(|e| !e.is_locked());
+        self.total_keys = unlocked.clone().count();
+        self.translated_keys = unlocked.filter(|e| e.is_translated()).count() as isize;
+    }
+
+    pub fn sidecar_path(&self) -> PathBuf {
+        self.original_path.with_extension("toml")
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        match self
+            .table_state
+            .selected()
+            .and_then(|row| self.rows.get(row))
+        {
+            Some(ViewRow::Entry { index, .. }) => Some(*index),
+            _ => None,
+        }
+    }
+
+    // Entradas afetadas por ações em lote: as marcadas, o namespace selecionado
+    // na árvore ou a linha atual.
+    pub fn action_targets(&self) -> Vec<usize> {
+        if !self.marked.is_empty() {
+            return self.marked.iter().copied().collect();
+        }
+        self.cursor_targets()
+    }
+
+    pub fn toggle_mark(&mut self) {
+        for index in self.cursor_targets() {
+            if !self.marked.remove(&index) {
+                self.marked.insert(index);
+            }
+        }
+    }
+
+    fn cursor_targets(&self) -> Vec<usize> {
+        if let Some(path) = self.selected_namespace() {
+            let prefix = format!("{}.", path);
+            return self
+                .view
+                .iter()
+                .copied()
+                .filter(|&i| self.entries[i].key.starts_with(&prefix))
+                .collect();
+        }
+        self.selected_index().into_iter().collect()
+    }
+
+    // Shift+↑/↓: marca a linha atual e a próxima na direção do movimento
+    pub fn extend_marks(&mut self, delta: isize) {
+        if let Some(index) = self.selected_index() {
+            self.marked.insert(index);
+        }
+        self.move_selection(delta);
+        if let Some(index) = self.selected_index() {
+            self.marked.insert(index);
+        }
+    }
+
+    pub fn selected_namespace(&self) -> Option<&str> {
+        match self
+            .table_state
+            .selected()
+            .and_then(|row| self.rows.get(row))
+        {
+            Some(ViewRow::Namespace { path, .. }) => Some(path),
+            _ => None,
+        }
+    }
+
+    // Seleciona a entrada, expandindo os namespaces que a escondem na árvore.
+    pub fn select_entry(&mut self, index: usize) -> bool {
+        if !self.view.contains(&index) {
+            return false;
+        }
+        if self.tree_mode {
+            let hidden: Vec<String> = tree::ancestors(&self.entries[index].key)
+                .into_iter()
+                .filter(|path| self.collapsed.contains(path))
+                .collect();
+            if !hidden.is_empty() {
+                for path in hidden {
+                    self.collapsed.remove(&path);
+                }
+                self.rebuild_rows();
+            }
+        }
+        let row = self
+            .rows
+            .iter()
+            .position(|row| matches!(row, ViewRow::Entry { index: i, .. } if *i == index));
+        self.table_state.select(row.or(self.table_state.selected()));
+        row.is_some()
+    }
+
+    fn select_namespace(&mut self, path: &str) -> bool {
+        let row = self
+            .rows
+            .iter()
+            .position(|row| matches!(row, ViewRow::Namespace { path: p, .. } if p == path));
+        if row.is_some() {
+            self.table_state.select(row);
+        }
+        row.is_some()
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let current = self.table_state.selected().unwrap_or(0) as isize;
+        let last = self.rows.len() as isize - 1;
+        self.table_state
+            .select(Some((current + delta).clamp(0, last) as usize));
+    }
+
+    // Vai para a próxima (ou anterior) entrada que satisfaz o predicado,
+    // voltando ao início/fim da tabela quando necessário.
+    pub fn jump_to(&mut self, forward: bool, predicate: impl Fn(&Entry) -> bool) -> bool {
+        let len = self.view.len();
+        if len == 0 {
+            return false;
+        }
+
+        let current = if let Some(index) = self.selected_index() {
+            self.view.iter().position(|&i| i == index).unwrap_or(0)
+        } else if let Some(path) = self.selected_namespace() {
+            // Num namespace, a primeira entrada dele também é candidata
+            let prefix = format!("{}.", path);
+            let first = self
+                .view
+                .iter()
+                .position(|&i| self.entries[i].key.starts_with(&prefix))
+                .unwrap_or(0);
+            if forward {
+                (first + len - 1) % len
+            } else {
+                first
+            }
+        } else {
+            0
+        };
+
+        for step in 1..=len {
+            let position = if forward {
+                (current + step) % len
+            } else {
+                (current + len - step % len) % len
+            };
+            let index = self.view[position];
+            if predicate(&self.entries[index]) {
+                return self.select_entry(index);
+            }
+        }
+        false
+    }
+
+    // Aceita um número de linha (começando em 1) ou uma chave, exata ou parcial.
+    pub fn go_to(&mut self, target: &str) -> bool {
+        let target = target.trim();
+        if let Ok(row) = target.parse::<usize>() {
+            if row >= 1 && row <= self.rows.len() {
+                self.table_state.select(Some(row - 1));
+                return true;
+            }
+            return false;
+        }
+
+        let exact = self
+            .view
+            .iter()
+            .find(|&&i| self.entries[i].key == target)
+            .copied();
+        let partial = || {
+            let target = target.to_lowercase();
+            self.view
+                .iter()
+                .find(|&&i| self.entries[i].key.to_lowercase().contains(&target))
+                .copied()
+        };
+        match exact.or_else(partial) {
+            Some(index) => self.select_entry(index),
+            None => false,
+        }
+    }
+
+    pub fn toggle_collapsed(&mut self, path: &str, collapse: bool) {
+        let changed = if collapse {
+            self.collapsed.insert(path.to_string())
+        } else {
+            self.collapsed.remove(path)
+        };
+        if changed {
+            self.rebuild_rows();
+            self.select_namespace(path);
+        }
+    }
+
+    // Seta para a esquerda na árvore: recolhe o namespace ou sobe para o pai
+    pub fn collapse_or_parent(&mut self) {
+        let (key, collapse) = match (self.selected_namespace(), self.selected_index()) {
+            (Some(path), _) => (path.to_string(), !self.collapsed.contains(path)),
+            (None, Some(index)) => (self.entries[index].key.clone(), false),
+            (None, None) => return,
+        };
+        if collapse {
+            self.toggle_collapsed(&key, true);
+        } else if let Some(parent) = tree::ancestors(&key).pop() {
+            self.select_namespace(&parent);
+        }
+    }
+
+    fn rebuild_rows(&mut self) {
+        if self.tree_mode {
+            let (order, rows) = tree::build(&self.entries, &self.view, &self.collapsed);
+            self.view = order;
+            self.rows = rows;
+        } else {
+            self.rows = self
+                .view
+                .iter()
+                .map(|&index| ViewRow::Entry { index, depth: 0 })
+                .collect();
+        }
+    }
+
+    // Ordena apenas a visão; `entries` continua na ordem do arquivo de origem,
+    // que é a usada ao salvar.
+    fn sort_view(&mut self) {
+        let entries = &self.entries;
+        match self.sort_mode {
+            SortMode::Original => {}
+            SortMode::Key => self
+                .view
+                .sort_by(|&a, &b| entries[a].key.cmp(&entries[b].key)),
+            SortMode::Status => self.view.sort_by_key(|&i| entries[i].status),
+            SortMode::Length => self.view.sort_by_key(|&i| {
+                std::cmp::Reverse(
+                    ui::format_json_value(&entries[i].translated)
+                        .chars()
+                        .count(),
+                )
+            }),
+            SortMode::Modified => self
+                .view
+                .sort_by_key(|&i| std::cmp::Reverse(entries[i].modified)),
+        }
+    }
+
+    // Reconstrói a visão filtrada mantendo a seleção; se a entrada selecionada
+    // saiu do filtro, a linha na mesma posição passa a ser a selecionada.
+    pub fn refresh_view(&mut self) {
+        let previous_row = self.table_state.selected();
+        let previous_entry = self.selected_index();
+        let previous_namespace = self.selected_namespace().map(str::to_string);
+
+        self.view = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| self.filter.matches(entry, &self.glossary))
+            .map(|(i, _)| i)
+            .collect();
+        self.sort_view();
+        self.rebuild_rows();
+
+        if let Some(index) = previous_entry
+            && self.view.contains(&index)
+        {
+            self.select_entry(index);
+            return;
+        }
+        if let Some(path) = previous_namespace
+            && self.select_namespace(&path)
+        {
+            return;
+        }
+
+        let row = if self.rows.is_empty() {
+            None
+        } else {
+            Some(previous_row.unwrap_or(0).min(self.rows.len() - 1))
+        };
+        self.table_state.select(row);
+    }
+}
+
+// Tela da memória de tradução: a primeira linha reúne todos os pares de idiomas
+pub struct MemoryScreenState {
+    pub pairs: Vec<(String, String, usize)>,
+    pub list_state: ListState,
+    // Remoção aguardando confirmação
+    pub confirm: Option<Purge>,
+    pub message: Option<String>,
 }
 
 pub struct SaveConfirmationState {
@@ -55,28 +697,68 @@ pub struct SaveConfirmationState {
     pub return_to: AppState,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Default)]
 pub struct TranslatedKeysData {
     pub keys: Vec<String>,
+    // Chaves marcadas para revisão
+    #[serde(default)]
+    pub review: Vec<String>,
+    // Chaves com rascunho da tradução automática
+    #[serde(default)]
+    pub draft: Vec<String>,
+    // Chaves bloqueadas pelo usuário, que não devem ser traduzidas
+    #[serde(default)]
+    pub locked: Vec<String>,
     pub last_updated: String,
+    // Data da última alteração de cada chave (RFC 3339)
+    #[serde(default)]
+    pub modified: BTreeMap<String, String>,
 }
 
 pub struct App {
     pub state: AppState,
     pub language: Language,
     pub locale: localization::Locale,
+    pub config: Config,
     pub file_selection: FileSelectionState,
     pub editing: Option<EditingState>,
     pub save_confirmation: Option<SaveConfirmationState>,
+    pub memory: TranslationMemory,
+    pub memory_screen: MemoryScreenState,
+    pub glossary: Rc<Glossary>,
+    pub speller: Speller,
 }
 
 impl App {
     pub fn new() -> Result<Self> {
         let language = Language::PT;
         let locale = localization::Locale::from_language(language.clone())?;
-        
-        let translation_suffix = locale.get("translation_suffix");
-        let files = file_operations::list_json_files(translation_suffix)?;
+
+        let config = Config::load()?;
+        // Memória, glossário ou dicionário inválidos viram aviso em vez de
+        // impedir a abertura
+        let mut warnings = Vec::new();
+        let memory = TranslationMemory::load(&config).unwrap_or_else(|error| {
+            warnings.push(
+                locale.get_with_params("memory_load_error", &[("error", &format!("{:#}", error))]),
+            );
+            TranslationMemory::disabled(&config)
+        });
+        let glossary = Glossary::load(&config).unwrap_or_else(|error| {
+            warnings.push(
+                locale
+                    .get_with_params("glossary_load_error", &[("error", &format!("{:#}", error))]),
+            );
+            Glossary::default()
+        });
+        let speller = Speller::load(&config).unwrap_or_else(|error| {
+            warnings.push(
+                locale
+                    .get_with_params("spelling_load_error", &[("error", &format!("{:#}", error))]),
+            );
+            Speller::disabled(&config)
+        });
+        let files = file_operations::list_source_files(&FormatContext::new(&locale, &config))?;
 
         let mut list_state = ListState::default();
         if !files.is_empty() {
@@ -87,18 +769,39 @@ impl App {
             state: AppState::FileSelection,
             language,
             locale,
-            file_selection: FileSelectionState { files, list_state },
+            config,
+            file_selection: FileSelectionState {
+                files,
+                list_state,
+                message: (!warnings.is_empty()).then(|| warnings.join(" ")),
+            },
             editing: None,
             save_confirmation: None,
+            memory,
+            memory_screen: MemoryScreenState {
+                pairs: Vec::new(),
+                list_state: ListState::default(),
+                confirm: None,
+                message: None,
+            },
+            glossary: Rc::new(glossary),
+            speller,
         })
     }
 
     pub fn check_notification_timeout(&mut self) {
-        if let Some(editing) = &mut self.editing {
-            if let Some(time) = editing.save_notification {
-                if time.elapsed() > Duration::from_secs(2) {
-                    editing.save_notification = None;
-                }
+        if let Some(editing) = &mut self.editing
+            && let Some(time) = editing.save_notification
+        {
+            // Mensagens com resultado de importação ficam mais tempo na tela
+            let timeout = if editing.notification_message.is_some() {
+                5
+            } else {
+                2
+            };
+            if time.elapsed() > Duration::from_secs(timeout) {
+                editing.save_notification = None;
+                editing.notification_message = None;
             }
         }
     }
@@ -107,11 +810,10 @@ impl App {
         if let Some(state) = &mut self.editing {
             let search_lower = state.search_query.to_lowercase();
             state.search_results = state
-                .entries
+                .view
                 .iter()
-                .enumerate()
-                .filter(|(_, entry)| entry.key.to_lowercase().contains(&search_lower))
-                .map(|(i, _)| i)
+                .copied()
+                .filter(|&i| state.entries[i].key.to_lowercase().contains(&search_lower))
                 .collect();
 
             state.search_selection = if !state.search_results.is_empty() {
@@ -122,28 +824,764 @@ impl App {
         }
     }
 
+    // Aplica a ação às entradas alvo (ver `action_targets`) como um único passo
+    // de desfazer, salvando o arquivo de chaves traduzidas uma só vez.
+    fn apply_to_targets(&mut self, action: impl Fn(&mut Entry)) -> Result<bool> {
+        let Some(state) = &mut self.editing else {
+            return Ok(false);
+        };
+
+        let mut step = Vec::new();
+        for index in state.action_targets() {
+            if state.entries[index].is_locked() {
+                continue;
+            }
+            let snapshot = state.snapshot(index);
+            let entry = &mut state.entries[index];
+            action(entry);
+            if entry.translated != snapshot.translated {
+                entry.touch();
+            } else if entry.status != snapshot.status {
+                entry.modified = Some(Local::now());
+            } else {
+                continue;
+            }
+            step.push(snapshot);
+        }
+        if step.is_empty() {
+            return Ok(false);
+        }
+
+        state.push_undo(step);
+        state.recount_translated();
+        file_operations::save_translated_keys(&state.sidecar_path(), &state.entries)?;
+        state.refresh_view();
+        Ok(true)
+    }
+
+    // Marca os alvos como traduzidos, ou desmarca se todos já estiverem.
     pub fn toggle_translation(&mut self) -> Result<()> {
+        let Some(state) = &self.editing else {
+            return Ok(());
+        };
+        let targets = state.action_targets();
+        let mark = !targets
+            .iter()
+            .all(|&i| state.entries[i].is_translated() || state.entries[i].is_locked());
+        let single = state.marked.is_empty() && state.selected_namespace().is_none();
+        let selected = state.selected_index();
+
+        let status = if mark {
+            EntryStatus::Translated
+        } else {
+            EntryStatus::Untranslated
+        };
+        // Marcar como traduzida uma entrada sem tradução aceita o texto de origem
+        if !self.apply_to_targets(|entry| {
+            entry.status = status;
+            if mark && !entry.has_translation() {
+                entry.translated = entry.original.clone();
+            }
+        })? {
+            return Ok(());
+        }
+
+        if let Some(state) = &mut self.editing
+            && mark
+            && single
+            && state.auto_advance
+        {
+            // Com o filtro de não traduzidas a seleção já cai na próxima pendente
+            let landed_on_pending = state
+                .selected_index()
+                .is_some_and(|i| Some(i) != selected && state.entries[i].is_pending());
+            if !landed_on_pending {
+                state.jump_to(true, Entry::is_pending);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn cycle_status(&mut self) -> Result<()> {
+        let Some(status) = self.editing.as_ref().and_then(|state| {
+            let first = *state.action_targets().first()?;
+            Some(state.entries[first].status.next())
+        }) else {
+            return Ok(());
+        };
+        self.apply_to_targets(|entry| entry.status = status)?;
+        Ok(())
+    }
+
+    pub fn copy_source_to_target(&mut self) -> Result<()> {
+        self.apply_to_targets(|entry| entry.translated = entry.original.clone())?;
+        Ok(())
+    }
+
+    pub fn clear_translation(&mut self) -> Result<()> {
+        self.apply_to_targets(|entry| entry.translated = Value::Null)?;
+        Ok(())
+    }
+
+    // Bloqueia os alvos, ou desbloqueia se todos já estiverem bloqueados. Os
+    // bloqueios vindos de `locked_keys` só saem pela configuração.
+    pub fn toggle_lock(&mut self) -> Result<()> {
+        let Some(state) = &mut self.editing else {
+            return Ok(());
+        };
+        let targets = state.action_targets();
+        if targets.is_empty() {
+            return Ok(());
+        }
+        let lock = !targets.iter().all(|&i| state.entries[i].is_locked());
+        let mut from_config = false;
+        for index in targets {
+            let entry = &mut state.entries[index];
+            match (lock, entry.lock) {
+                (true, Lock::Unlocked) => entry.lock = Lock::Manual,
+                (false, Lock::Manual) => entry.lock = Lock::Unlocked,
+                (false, Lock::Config) => from_config = true,
+                _ => {}
+            }
+        }
+        state.recount_translated();
+        file_operations::save_translated_keys(&state.sidecar_path(), &state.entries)?;
+        state.refresh_view();
+        if from_config {
+            state.notify(self.locale.get("lock_from_config").to_string());
+        }
+        Ok(())
+    }
+
+    pub fn toggle_tree_mode(&mut self) {
         if let Some(state) = &mut self.editing {
-            if let Some(selected) = state.table_state.selected() {
-                if let Some(entry) = state.entries.get_mut(selected) {
-                    entry.is_translated = !entry.is_translated;
-                    state.translated_keys += if entry.is_translated { 1 } else { -1 };
-                    let toml_path = state.original_path.with_extension("toml");
-                    file_operations::save_translated_keys(&toml_path, &state.entries)?;
+            state.tree_mode = !state.tree_mode;
+            state.refresh_view();
+        }
+    }
+
+    pub fn undo(&mut self) -> Result<()> {
+        if let Some(state) = &mut self.editing
+            && let Some(step) = state.undo_stack.pop()
+        {
+            let mut status_changed = false;
+            for snapshot in step.into_iter().rev() {
+                if let Some(entry) = state.entries.get_mut(snapshot.index) {
+                    status_changed |= entry.status != snapshot.status;
+                    if entry.translated != snapshot.translated {
+                        entry.touch();
+                    }
+                    entry.translated = snapshot.translated;
+                    entry.status = snapshot.status;
                 }
             }
+            if status_changed {
+                state.recount_translated();
+                file_operations::save_translated_keys(&state.sidecar_path(), &state.entries)?;
+            }
+            state.refresh_view();
         }
         Ok(())
     }
 
+    pub fn update_replace_preview(&mut self) {
+        if let Some(state) = &mut self.editing
+            && let Some(replace) = &mut state.replace
+        {
+            match find_replace::build_preview(&state.entries, &replace.find, &replace.replace) {
+                Ok(matches) => {
+                    replace.matches = matches;
+                    replace.selection = 0;
+                    replace.preview = true;
+                    replace.error = None;
+                }
+                Err(err) => replace.error = Some(err.to_string()),
+            }
+        }
+    }
+
+    // Aplica as substituições aceitas na prévia como um único passo de desfazer.
+    pub fn apply_replace(&mut self, accept_all: bool) {
+        if let Some(state) = &mut self.editing
+            && let Some(replace) = state.replace.take()
+        {
+            let mut step = Vec::new();
+            for found in replace.matches {
+                if !accept_all && !found.accepted {
+                    continue;
+                }
+                step.push(state.snapshot(found.entry_index));
+                let entry = &mut state.entries[found.entry_index];
+                entry.translated = Value::String(found.result);
+                entry.touch();
+            }
+            state.push_undo(step);
+            state.refresh_view();
+        }
+    }
+
+    pub fn toggle_filter(&mut self, option: char) {
+        if let Some(state) = &mut self.editing {
+            let filter = &mut state.filter;
+            match option {
+                '1' => filter.untranslated = !filter.untranslated,
+                '2' => filter.translated = !filter.translated,
+                '3' => filter.dirty = !filter.dirty,
+                '4' => filter.issues = !filter.issues,
+                '5' => filter.empty = !filter.empty,
+                '6' => filter.identical = !filter.identical,
+                _ => return,
+            }
+            state.refresh_view();
+        }
+    }
+
+    pub fn cycle_sort(&mut self) {
+        if let Some(state) = &mut self.editing {
+            state.sort_mode = state.sort_mode.next();
+            state.refresh_view();
+        }
+    }
+
     pub fn save_current_file(&mut self) -> Result<()> {
         if let Some(state) = &mut self.editing {
-            file_operations::save_translated_json(
-                state,
+            let context = FormatContext::new(&self.locale, &self.config);
+            state
+                .format
+                .save(&mut state.document, &state.entries, &context)?;
+            file_operations::save_translated_keys(&state.sidecar_path(), &state.entries)?;
+            self.memory
+                .add_entries(&state.entries, &state.original_path, &self.config)?;
+            state.memory_for = None;
+            for entry in &mut state.entries {
+                entry.dirty = false;
+            }
+            state.refresh_view();
+            state.save_notification = Some(Instant::now());
+            state.notification_message = None;
+        }
+        Ok(())
+    }
+
+    // Busca as sugestões da memória quando a entrada selecionada muda
+    pub fn update_memory_matches(&mut self) {
+        let Some(state) = &mut self.editing else {
+            return;
+        };
+        let selected = state.selected_index();
+        if selected == state.memory_for && selected.is_some() {
+            return;
+        }
+        state.memory_for = selected;
+        state.memory_matches = match selected {
+            Some(index) => {
+                let entry = &state.entries[index];
+                let file = fs::canonicalize(&state.original_path)
+                    .unwrap_or_else(|_| state.original_path.clone())
+                    .display()
+                    .to_string();
+                self.memory.lookup(
+                    &formats::text_of(&entry.original),
+                    &self.config,
+                    (&file, &entry.key),
+                    MEMORY_SUGGESTIONS,
+                )
+            }
+            None => Vec::new(),
+        };
+    }
+
+    // Usa a melhor sugestão da memória como tradução da entrada selecionada
+    pub fn apply_memory_match(&mut self) {
+        if let Some(state) = &mut self.editing
+            && let Some(index) = state.memory_for
+            && let Some(found) = state.memory_matches.first()
+        {
+            let translated =
+                review::typed_value(&state.entries[index].original, found.target.clone());
+            let step = state.apply_change(index, Some(translated), None);
+            state.push_undo(step.into_iter().collect());
+            state.refresh_view();
+        }
+    }
+
+    // Traduz a entrada selecionada e abre a edição com o resultado. Confirmado
+    // sem alterações, o texto fica como rascunho.
+    pub fn machine_translate_selected(&mut self) {
+        let Some(state) = &mut self.editing else {
+            return;
+        };
+        let Some(index) = state.selected_index() else {
+            return;
+        };
+        if state.entries[index].is_locked() {
+            state.notify(self.locale.get("entry_locked").to_string());
+            return;
+        }
+        let Value::String(original) = &state.entries[index].original else {
+            return;
+        };
+        let result = machine::provider(&self.config).and_then(|provider| {
+            provider.translate(
+                std::slice::from_ref(original),
+                &self.config.source_language,
+                &self.config.target_language,
+            )
+        });
+        match result {
+            Ok(mut translated) => {
+                let text = translated.remove(0);
+                state.editing = Some(index);
+                state.cursor_pos = text.chars().count();
+                state.input = text.clone();
+                state.machine_input = Some(text);
+            }
+            Err(error) => state.notify(
+                self.locale
+                    .get_with_params("mt_error", &[("error", &error.to_string())]),
+            ),
+        }
+    }
+
+    // Cria rascunhos da tradução automática para todas as entradas sem
+    // tradução, como um único passo de desfazer
+    pub fn machine_translate_pending(&mut self) -> Result<()> {
+        let Some(state) = &mut self.editing else {
+            return Ok(());
+        };
+        let (indices, texts): (Vec<usize>, Vec<String>) = state
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let Value::String(original) = &entry.original else {
+                    return None;
+                };
+                let pending = entry.status == EntryStatus::Untranslated
+                    && !entry.is_locked()
+                    && !original.trim().is_empty()
+                    && formats::text_of(&entry.translated).is_empty();
+                pending.then(|| (index, original.clone()))
+            })
+            .unzip();
+        if indices.is_empty() {
+            state.notify(self.locale.get("mt_nothing_pending").to_string());
+            return Ok(());
+        }
+
+        let result = machine::provider(&self.config)
+            .and_then(|provider| machine::translate_all(provider.as_ref(), &texts, &self.config));
+        let message = match result {
+            Ok(translated) => {
+                let mut step = Vec::new();
+                for (index, text) in indices.into_iter().zip(translated) {
+                    if text.trim().is_empty() {
+                        continue;
+                    }
+                    step.extend(state.apply_change(
+                        index,
+                        Some(Value::String(text)),
+                        Some(EntryStatus::Draft),
+                    ));
+                }
+                let drafted = step.len();
+                state.push_undo(step);
+                state.recount_translated();
+                file_operations::save_translated_keys(&state.sidecar_path(), &state.entries)?;
+                state.refresh_view();
+                self.locale
+                    .get_with_params("mt_drafted", &[("count", &drafted.to_string())])
+            }
+            Err(error) => self
+                .locale
+                .get_with_params("mt_error", &[("error", &error.to_string())]),
+        };
+        state.notify(message);
+        Ok(())
+    }
+
+    fn xliff_path(&self) -> Option<PathBuf> {
+        self.editing.as_ref().map(|state| {
+            file_operations::xliff_path(
+                &state.original_path,
                 self.locale.get("translations_folder"),
                 self.locale.get("translation_suffix"),
-            )?;
-            state.save_notification = Some(Instant::now());
+            )
+        })
+    }
+
+    pub fn export_xliff(&mut self) -> Result<()> {
+        let Some(path) = self.xliff_path() else {
+            return Ok(());
+        };
+        if let Some(state) = &mut self.editing {
+            file_operations::export_xliff(state, &path, &self.config)?;
+            let message = self
+                .locale
+                .get_with_params("xliff_exported", &[("path", &path.display().to_string())]);
+            state.notify(message);
+        }
+        Ok(())
+    }
+
+    // Aplica as traduções e estados de um XLIFF devolvido e grava o resultado.
+    // Unidades sem chave correspondente são listadas na notificação.
+    pub fn import_xliff(&mut self) -> Result<()> {
+        let Some(path) = self.xliff_path() else {
+            return Ok(());
+        };
+        let path_text = path.display().to_string();
+        if !path.exists() {
+            let message = self
+                .locale
+                .get_with_params("xliff_missing", &[("path", &path_text)]);
+            if let Some(state) = &mut self.editing {
+                state.notify(message);
+            }
+            return Ok(());
+        }
+        let units = match file_operations::load_xliff(&path) {
+            Ok(units) => units,
+            Err(error) => {
+                let message = self
+                    .locale
+                    .get_with_params("xliff_error", &[("error", &error.to_string())]);
+                if let Some(state) = &mut self.editing {
+                    state.notify(message);
+                }
+                return Ok(());
+            }
+        };
+        let Some(state) = &mut self.editing else {
+            return Ok(());
+        };
+
+        let by_key: HashMap<String, usize> = state
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (entry.key.clone(), index))
+            .collect();
+        let mut unmatched = Vec::new();
+        let mut step = Vec::new();
+        for unit in units {
+            let Some(&index) = by_key.get(&unit.key) else {
+                unmatched.push(unit.key);
+                continue;
+            };
+            let translated = unit
+                .target
+                .map(|target| review::typed_value(&state.entries[index].original, target));
+            step.extend(state.apply_change(index, translated, unit.status));
+        }
+
+        let updated = step.len();
+        state.push_undo(step);
+        state.recount_translated();
+        self.save_current_file()?;
+
+        let mut message = self.locale.get_with_params(
+            "xliff_imported",
+            &[
+                ("updated", &updated.to_string()),
+                ("unmatched", &unmatched.len().to_string()),
+            ],
+        );
+        message.push_str(&unmatched_summary(&unmatched));
+        if let Some(state) = &mut self.editing {
+            state.notify(message);
+        }
+        Ok(())
+    }
+
+    fn spreadsheet_path(&self) -> Option<PathBuf> {
+        self.editing.as_ref().map(|state| {
+            file_operations::spreadsheet_path(
+                &state.original_path,
+                self.locale.get("translations_folder"),
+                self.locale.get("translation_suffix"),
+                &self.config,
+            )
+        })
+    }
+
+    pub fn export_spreadsheet(&mut self) -> Result<()> {
+        let Some(path) = self.spreadsheet_path() else {
+            return Ok(());
+        };
+        if let Some(state) = &mut self.editing {
+            file_operations::export_spreadsheet(state, &path, &self.config)?;
+            let message = self.locale.get_with_params(
+                "spreadsheet_exported",
+                &[("path", &path.display().to_string())],
+            );
+            state.notify(message);
+        }
+        Ok(())
+    }
+
+    // Lê a planilha devolvida e abre a prévia com as células que diferem do
+    // estado atual. Nada é alterado até a confirmação em `apply_review`.
+    pub fn import_spreadsheet(&mut self) -> Result<()> {
+        let Some(path) = self.spreadsheet_path() else {
+            return Ok(());
+        };
+        let message = if !path.exists() {
+            Some(self.locale.get_with_params(
+                "spreadsheet_missing",
+                &[("path", &path.display().to_string())],
+            ))
+        } else {
+            match file_operations::load_spreadsheet(&path) {
+                Ok(rows) => {
+                    let Some(state) = &mut self.editing else {
+                        return Ok(());
+                    };
+                    let (changes, unmatched) = propose_changes(
+                        &state.entries,
+                        rows.into_iter()
+                            .map(|row| (row.key, row.translated, row.status)),
+                    );
+                    if changes.is_empty() {
+                        let mut message = self.locale.get_with_params(
+                            "spreadsheet_no_changes",
+                            &[("unmatched", &unmatched.len().to_string())],
+                        );
+                        message.push_str(&unmatched_summary(&unmatched));
+                        Some(message)
+                    } else {
+                        state.review = Some(ReviewState::new(changes, unmatched));
+                        None
+                    }
+                }
+                Err(error) => Some(
+                    self.locale
+                        .get_with_params("spreadsheet_error", &[("error", &error.to_string())]),
+                ),
+            }
+        };
+        if let Some(message) = message
+            && let Some(state) = &mut self.editing
+        {
+            state.notify(message);
+        }
+        Ok(())
+    }
+
+    // Acrescenta as palavras desconhecidas da tradução selecionada ao
+    // dicionário pessoal do projeto
+    pub fn add_to_dictionary(&mut self) {
+        let Some(state) = &mut self.editing else {
+            return;
+        };
+        let Some(index) = state.selected_index() else {
+            return;
+        };
+        let speller = &mut self.speller;
+        let words = speller.misspelled_words(&formats::text_of(&state.entries[index].translated));
+        let message = if words.is_empty() {
+            self.locale.get("spelling_nothing").to_string()
+        } else {
+            match speller.add_words(&words) {
+                Ok(()) => {
+                    state.spelling.clear();
+                    self.locale
+                        .get_with_params("spelling_added", &[("words", &words.join(", "))])
+                }
+                Err(error) => self
+                    .locale
+                    .get_with_params("spelling_error", &[("error", &error.to_string())]),
+            }
+        };
+        state.notify(message);
+    }
+
+    // Envia as entradas marcadas, ou todas, ao comando externo e abre a prévia
+    // com as sugestões que alteram alguma entrada
+    pub fn run_hook(&mut self) {
+        let Some(state) = &mut self.editing else {
+            return;
+        };
+        let indices: Vec<usize> = if state.marked.is_empty() {
+            (0..state.entries.len()).collect()
+        } else {
+            state.marked.iter().copied().collect()
+        };
+        let indices: Vec<usize> = indices
+            .into_iter()
+            .filter(|&index| !state.entries[index].is_locked())
+            .collect();
+        let result = hook::run(
+            &self.config,
+            &state.original_path,
+            indices.iter().map(|&index| &state.entries[index]),
+        );
+        let message = match result {
+            Ok(suggestions) => {
+                let (changes, unmatched) = propose_changes(
+                    &state.entries,
+                    suggestions.into_iter().map(|suggestion| {
+                        let status = suggestion.status();
+                        (suggestion.key, suggestion.translation, status)
+                    }),
+                );
+                if changes.is_empty() {
+                    let mut message = self.locale.get_with_params(
+                        "hook_no_changes",
+                        &[("unmatched", &unmatched.len().to_string())],
+                    );
+                    message.push_str(&unmatched_summary(&unmatched));
+                    message
+                } else {
+                    state.review = Some(ReviewState::new(changes, unmatched));
+                    return;
+                }
+            }
+            Err(error) => self
+                .locale
+                .get_with_params("hook_error", &[("error", &format!("{:#}", error))]),
+        };
+        state.notify(message);
+    }
+
+    // Aplica as alterações aceitas na prévia como um único passo de desfazer
+    pub fn apply_review(&mut self, accept_all: bool) -> Result<()> {
+        let Some(state) = &mut self.editing else {
+            return Ok(());
+        };
+        let Some(review) = state.review.take() else {
+            return Ok(());
+        };
+        let mut step = Vec::new();
+        for change in review.changes {
+            if !accept_all && !change.accepted {
+                continue;
+            }
+            step.extend(state.apply_change(change.entry_index, change.translated, change.status));
+        }
+        let applied = step.len();
+        state.push_undo(step);
+        state.recount_translated();
+        file_operations::save_translated_keys(&state.sidecar_path(), &state.entries)?;
+        state.refresh_view();
+        let message = self
+            .locale
+            .get_with_params("review_applied", &[("count", &applied.to_string())]);
+        state.notify(message);
+        Ok(())
+    }
+
+    pub fn open_memory_screen(&mut self) {
+        self.memory_screen.confirm = None;
+        self.memory_screen.message = None;
+        self.refresh_memory_pairs();
+        self.state = AppState::Memory;
+    }
+
+    fn refresh_memory_pairs(&mut self) {
+        let screen = &mut self.memory_screen;
+        screen.pairs = self.memory.pairs();
+        let selected = screen.list_state.selected().unwrap_or(0);
+        screen
+            .list_state
+            .select(Some(selected.min(screen.pairs.len())));
+    }
+
+    // Par de idiomas da linha selecionada; `None` na linha de todos os pares
+    fn selected_memory_pair(&self) -> Option<&(String, String, usize)> {
+        let selected = self.memory_screen.list_state.selected()?;
+        self.memory_screen.pairs.get(selected.checked_sub(1)?)
+    }
+
+    // Erros de leitura e gravação aparecem na tela da memória
+    pub fn export_tmx(&mut self) {
+        let pair = self
+            .selected_memory_pair()
+            .map(|(source, target, _)| (source.as_str(), target.as_str()));
+        let segments = self.memory.segments(pair);
+        let path = Path::new(&self.config.tmx_path);
+        let written = path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(path, tmx::export(&segments)));
+        let message = match written {
+            Ok(()) => self.locale.get_with_params(
+                "tmx_exported",
+                &[
+                    ("count", &segments.len().to_string()),
+                    ("path", &self.config.tmx_path),
+                ],
+            ),
+            Err(error) => self
+                .locale
+                .get_with_params("tmx_export_error", &[("error", &error.to_string())]),
+        };
+        self.memory_screen.message = Some(message);
+    }
+
+    pub fn import_tmx(&mut self) {
+        let path = Path::new(&self.config.tmx_path);
+        let message = if !path.exists() {
+            self.locale
+                .get_with_params("tmx_missing", &[("path", &self.config.tmx_path)])
+        } else {
+            let imported = formats::read(path)
+                .and_then(|content| tmx::import(&content))
+                .and_then(|segments| self.memory.import(segments));
+            match imported {
+                Ok((added, duplicates)) => {
+                    self.refresh_memory_pairs();
+                    self.locale.get_with_params(
+                        "tmx_imported",
+                        &[
+                            ("added", &added.to_string()),
+                            ("duplicates", &duplicates.to_string()),
+                        ],
+                    )
+                }
+                Err(error) => self
+                    .locale
+                    .get_with_params("tmx_error", &[("error", &format!("{:#}", error))]),
+            }
+        };
+        self.memory_screen.message = Some(message);
+    }
+
+    // Pede confirmação para remover o par selecionado (ou todos) ou os
+    // segmentos de arquivos que não existem mais
+    pub fn request_purge(&mut self, orphaned: bool) {
+        let purge = if orphaned {
+            Purge::Orphaned
+        } else {
+            match self.selected_memory_pair() {
+                Some((source, target, _)) => Purge::Pair(source.clone(), target.clone()),
+                None => Purge::All,
+            }
+        };
+        let scope = match &purge {
+            Purge::All => self.locale.get("purge_scope_all").to_string(),
+            Purge::Pair(source, target) => format!("{} → {}", source, target),
+            Purge::Orphaned => self.locale.get("purge_scope_orphaned").to_string(),
+        };
+        self.memory_screen.message = Some(self.locale.get_with_params(
+            "purge_confirm",
+            &[
+                ("count", &self.memory.count(&purge).to_string()),
+                ("scope", &scope),
+            ],
+        ));
+        self.memory_screen.confirm = Some(purge);
+    }
+
+    pub fn confirm_purge(&mut self) -> Result<()> {
+        if let Some(purge) = self.memory_screen.confirm.take() {
+            let removed = self.memory.purge(&purge)?;
+            self.refresh_memory_pairs();
+            self.memory_screen.message = Some(
+                self.locale
+                    .get_with_params("purged", &[("count", &removed.to_string())]),
+            );
         }
         Ok(())
     }
@@ -165,3 +1603,79 @@ impl App {
         Ok(())
     }
 }
+
+// Localiza as entradas pela chave e monta as alterações propostas para a
+// prévia. Retorna também as chaves que não existem no arquivo.
+fn propose_changes(
+    entries: &[Entry],
+    rows: impl IntoIterator<Item = (String, Option<String>, Option<EntryStatus>)>,
+) -> (Vec<review::ProposedChange>, Vec<String>) {
+    let by_key: HashMap<&str, usize> = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| (entry.key.as_str(), index))
+        .collect();
+    let mut changes = Vec::new();
+    let mut unmatched = Vec::new();
+    for (key, translated, status) in rows {
+        match by_key.get(key.as_str()) {
+            Some(&index) => {
+                changes.extend(review::propose(&entries[index], index, translated, status))
+            }
+            None => unmatched.push(key),
+        }
+    }
+    (changes, unmatched)
+}
+
+// Lista curta das chaves sem correspondência, para as notificações de importação
+fn unmatched_summary(unmatched: &[String]) -> String {
+    if unmatched.is_empty() {
+        return String::new();
+    }
+    let mut keys = unmatched
+        .iter()
+        .take(5)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if unmatched.len() > 5 {
+        keys.push_str(", …");
+    }
+    format!(": {}", keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::registry::FORMATS;
+
+    #[test]
+    fn confirming_an_unchanged_edit_keeps_newlines_and_quotes() {
+        let text = "Primeira linha\nEle disse \"olá\"";
+        let entry = Entry {
+            key: "notice".to_string(),
+            original: Value::String("First line\nHe said \"hi\"".to_string()),
+            translated: Value::String(text.to_string()),
+            status: EntryStatus::Translated,
+            lock: Lock::Unlocked,
+            max_length: None,
+            dirty: false,
+            modified: None,
+            notes: Vec::new(),
+        };
+        let mut state = EditingState::new(
+            PathBuf::from("notice.yml"),
+            vec![entry],
+            Document::new(()),
+            FORMATS[0],
+            Rc::new(Glossary::default()),
+        );
+
+        state.start_editing(0);
+        assert_eq!(state.input, text);
+        state.apply_input(0);
+        assert_eq!(state.entries[0].translated, Value::String(text.to_string()));
+        assert!(!state.entries[0].dirty);
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, path::Path};
+
+// Configuração opcional do projeto, lida de `transtui.toml` na pasta atual
+const CONFIG_FILE: &str = "transtui.toml";
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct Config {
+    // Código do idioma de destino (`pt`, `pt-BR`...), usado nos formatos que
+    // identificam o idioma no conteúdo ou no caminho do arquivo
+    pub target_language: String,
+    pub source_language: String,
+    // Versão do XLIFF exportado: "1.2" ou "2.0"
+    pub xliff_version: String,
+    // Planilha exportada: "csv" ou "tsv", com ou sem BOM UTF-8 para o Excel
+    pub spreadsheet_format: String,
+    pub spreadsheet_bom: bool,
+    // Grava o JSON traduzido com a formatação do arquivo já existente, em vez
+    // da formatação da origem
+    pub json_keep_output_style: bool,
+    // Caminho das traduções, como `{dir}/locales/{lang}/{stem}.{ext}` ou
+    // `{dir}/{stem}.{lang}.{ext}`. Sem ele, a pasta de traduções é usada.
+    pub output_template: Option<String>,
+    // Arquivo da memória de tradução; por padrão fica na pasta de dados do usuário
+    pub memory_path: Option<String>,
+    // Semelhança mínima (%) para uma sugestão da memória aparecer
+    pub memory_threshold: u8,
+    // Arquivo TMX usado para importar e exportar a memória
+    pub tmx_path: String,
+    // Glossário em CSV/TSV ou TOML, com termos exigidos e proibidos
+    pub glossary_path: Option<String>,
+    // Tradução automática: provedor ("libretranslate"), endereço do servidor,
+    // chave de API opcional e tempo limite de cada requisição em segundos
+    pub mt_provider: String,
+    pub mt_url: Option<String>,
+    pub mt_api_key: Option<String>,
+    pub mt_timeout: u64,
+    // Comando externo que recebe as entradas em JSON no stdin e devolve
+    // sugestões no stdout, com o tempo limite em segundos
+    pub hook_command: Option<String>,
+    pub hook_timeout: u64,
+    // Como gravar as chaves ainda sem tradução: "source" (texto de origem),
+    // "empty" (texto vazio) ou "omit" (fora do arquivo). "omit" só vale para
+    // JSON e YAML; os recursos do Android, iOS, Java e Fluent usam o texto de
+    // origem no lugar. O PO sempre grava o `msgstr` vazio.
+    pub untranslated_output: String,
+    // Chaves que não devem ser traduzidas, com `*` como curinga (`brand.*`,
+    // `*_url`). Além destas, cada entrada pode ser bloqueada na edição.
+    pub locked_keys: Vec<String>,
+    // Tamanho máximo da tradução, em caracteres, por padrão de chave com `*`
+    // como curinga (`"button.*" = 20`). Quando vários casam, vale o menor.
+    pub length_limits: BTreeMap<String, usize>,
+    // Arquivo JSON ou TOML com limites no mesmo formato, que prevalecem sobre
+    // os de `length_limits`
+    pub length_limits_path: Option<String>,
+    // Verificação ortográfica: pasta (ou arquivo `.aff`/`.dic`) com o
+    // dicionário Hunspell do idioma de destino e o dicionário pessoal do
+    // projeto, com uma palavra por linha
+    pub spell_path: Option<String>,
+    pub spell_personal_path: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            target_language: "pt".to_string(),
+            source_language: "en".to_string(),
+            xliff_version: "1.2".to_string(),
+            spreadsheet_format: "csv".to_string(),
+            spreadsheet_bom: true,
+            json_keep_output_style: false,
+            output_template: None,
+            memory_path: None,
+            memory_threshold: 60,
+            tmx_path: "memory.tmx".to_string(),
+            glossary_path: None,
+            mt_provider: "libretranslate".to_string(),
+            mt_url: None,
+            mt_api_key: None,
+            mt_timeout: 30,
+            hook_command: None,
+            hook_timeout: 60,
+            untranslated_output: "source".to_string(),
+            locked_keys: Vec::new(),
+            length_limits: BTreeMap::new(),
+            length_limits_path: None,
+            spell_path: None,
+            spell_personal_path: "transtui.words".to_string(),
+        }
+    }
+}
+
+impl Config {
+    // Padrões de `locked_keys` convertidos em regex
+    pub fn locked_patterns(&self) -> Vec<Regex> {
+        self.locked_keys
+            .iter()
+            .filter_map(|pattern| key_pattern(pattern))
+            .collect()
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Path::new(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        toml::from_str(&content).with_context(|| format!("invalid {}", CONFIG_FILE))
+    }
+}
+
+// Padrão de chave com `*` como curinga, convertido em regex ancorada na chave inteira
+pub fn key_pattern(pattern: &str) -> Option<Regex> {
+    let pattern = regex::escape(pattern).replace(r"\*", ".*");
+    Regex::new(&format!("^{}$", pattern)).ok()
+}
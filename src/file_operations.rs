@@ -1,63 +1,267 @@
-use anyhow::Result;
-use chrono::Local;
-use serde_json::{Map, Value};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use regex::Regex;
+use serde_json::Value;
 use std::{
+    collections::{BTreeMap, HashMap, HashSet},
     fs,
     path::{Path, PathBuf},
 };
-use toml;
 
-use crate::app::{Entry, TranslatedKeysData, EditingState};
+use crate::app::{EditingState, Entry, EntryStatus, Lock, TranslatedKeysData};
+use crate::config::{self, Config};
+use crate::formats::{
+    registry::{self, FormatContext},
+    spreadsheet::{self, SheetRow},
+    text_of,
+    xliff::{self, Languages, Version, XliffUnit},
+};
+
+// Subpastas percorridas abaixo da pasta atual (`res/values`, `locales/en`)
+const MAX_DEPTH: usize = 4;
+// Pastas de build e dependências; as ocultas (`.git`) também ficam de fora
+const SKIPPED_DIRS: [&str; 2] = ["target", "node_modules"];
 
-pub fn list_json_files(translation_suffix: &str) -> Result<Vec<PathBuf>> {
+// Arquivos da pasta atual e das subpastas reconhecidos por algum formato,
+// exceto as traduções geradas a partir de outro arquivo da lista
+pub fn list_source_files(context: &FormatContext) -> Result<Vec<PathBuf>> {
+    source_files(Path::new("."), context)
+}
+
+fn source_files(root: &Path, context: &FormatContext) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
-    for entry in fs::read_dir(".")? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_file()
-            && path.extension().unwrap_or_default() == "json"
+    walk(root, 0, &mut files)?;
+    files.retain(|path| {
+        registry::find(path).is_some()
             && !path
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .ends_with(&format!("_{}.json", translation_suffix))
-        {
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .ends_with(&format!("_{}", context.translation_suffix))
+    });
+    files.sort();
+
+    // Cada formato sabe onde grava a tradução; arquivos editados no lugar (`.po`)
+    // não excluem a si mesmos
+    let outputs: HashSet<PathBuf> = files
+        .iter()
+        .filter_map(|path| {
+            let output = relative(&registry::find(path)?.output_path(path, context));
+            (output != relative(path)).then_some(output)
+        })
+        .collect();
+    files.retain(|path| !outputs.contains(&relative(path)));
+    Ok(files)
+}
+
+fn relative(path: &Path) -> PathBuf {
+    path.strip_prefix(".").unwrap_or(path).to_path_buf()
+}
+
+// Arquivos de `dir` e das subpastas até `MAX_DEPTH`, sem o `./` inicial para
+// que `{dir}` seja a pasta real do arquivo. Subpastas ilegíveis são ignoradas.
+fn walk(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = relative(&entry?.path());
+        if path.is_file() {
             files.push(path);
+        } else if path.is_dir() && depth < MAX_DEPTH {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref()) {
+                let _ = walk(&path, depth + 1, files);
+            }
         }
     }
-    Ok(files)
+    Ok(())
+}
+
+fn translated_path(
+    original_path: &Path,
+    translations_folder: &str,
+    translation_suffix: &str,
+    extension: &str,
+) -> PathBuf {
+    let filename = format!(
+        "{}_{}.{}",
+        original_path.file_stem().unwrap().to_str().unwrap(),
+        translation_suffix,
+        extension
+    );
+    Path::new(translations_folder).join(filename)
+}
+
+// Caminho da tradução de um arquivo de origem. Sem `output_template` na
+// configuração, é `<pasta>/<nome>_<sufixo>.<ext>`; com ele, o modelo com
+// `{dir}`, `{stem}`, `{lang}` e `{ext}` substituídos.
+pub fn output_path(original_path: &Path, context: &FormatContext, extension: &str) -> PathBuf {
+    let Some(template) = &context.config.output_template else {
+        return translated_path(
+            original_path,
+            context.translations_folder,
+            context.translation_suffix,
+            extension,
+        );
+    };
+    let dir = original_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let stem = original_path.file_stem().unwrap_or_default();
+    PathBuf::from(
+        template
+            .replace("{dir}", &dir.to_string_lossy())
+            .replace("{stem}", &stem.to_string_lossy())
+            .replace("{lang}", &context.config.target_language)
+            .replace("{ext}", extension),
+    )
 }
 
-pub fn load_translated_keys(path: &Path) -> Result<Vec<String>> {
+pub fn load_translated_keys(path: &Path) -> Result<TranslatedKeysData> {
     if path.exists() {
         let content = fs::read_to_string(path)?;
 
         if path.extension().unwrap_or_default() == "toml" {
-            let data: TranslatedKeysData = toml::from_str(&content)?;
-            Ok(data.keys)
+            Ok(toml::from_str(&content)?)
         } else {
-            Ok(content
-                .split(';')
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect())
+            Ok(TranslatedKeysData {
+                keys: content
+                    .split(';')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+                ..Default::default()
+            })
         }
     } else {
-        Ok(Vec::new())
+        Ok(TranslatedKeysData::default())
+    }
+}
+
+pub fn parse_modified_times(
+    modified: &BTreeMap<String, String>,
+) -> HashMap<String, DateTime<Local>> {
+    modified
+        .iter()
+        .filter_map(|(key, time)| {
+            DateTime::parse_from_rfc3339(time)
+                .ok()
+                .map(|time| (key.clone(), time.with_timezone(&Local)))
+        })
+        .collect()
+}
+
+// Aplica os status e horários registrados no arquivo auxiliar. Chaves não
+// traduzidas e nunca editadas voltam a ficar sem tradução, já que o arquivo
+// de saída pode tê-las gravado com o texto de origem ou vazias.
+pub fn apply_translated_keys(entries: &mut [Entry], keys_data: &TranslatedKeysData) {
+    let modified_times = parse_modified_times(&keys_data.modified);
+    for entry in entries {
+        entry.status = if keys_data.keys.contains(&entry.key) {
+            EntryStatus::Translated
+        } else if keys_data.review.contains(&entry.key) {
+            EntryStatus::NeedsReview
+        } else if keys_data.draft.contains(&entry.key) {
+            EntryStatus::Draft
+        } else {
+            EntryStatus::Untranslated
+        };
+        entry.modified = modified_times.get(&entry.key).copied();
+        if entry.status == EntryStatus::Untranslated
+            && entry.modified.is_none()
+            && (entry.translated == entry.original || text_of(&entry.translated).is_empty())
+        {
+            entry.translated = Value::Null;
+        }
+    }
+}
+
+// Estados, bloqueios e limites de tamanho guardados no arquivo auxiliar
+// (`.toml`) ao lado da origem e na configuração
+pub fn apply_sidecar(entries: &mut [Entry], path: &Path, config: &Config) -> Result<()> {
+    let keys_data = load_translated_keys(&path.with_extension("toml"))?;
+    apply_translated_keys(entries, &keys_data);
+    apply_locks(entries, &keys_data, config);
+    apply_length_limits(entries, config)
+}
+
+// Bloqueia as entradas marcadas no arquivo auxiliar e as que casam com os
+// padrões de `locked_keys`; os padrões prevalecem
+pub fn apply_locks(entries: &mut [Entry], keys_data: &TranslatedKeysData, config: &Config) {
+    let patterns = config.locked_patterns();
+    for entry in entries {
+        entry.lock = if patterns.iter().any(|pattern| pattern.is_match(&entry.key)) {
+            Lock::Config
+        } else if keys_data.locked.contains(&entry.key) {
+            Lock::Manual
+        } else {
+            Lock::Unlocked
+        };
     }
 }
 
+// Limites de tamanho por chave: os do arquivo de `length_limits_path`
+// prevalecem sobre os da configuração; entre padrões que casam, vale o menor
+pub fn apply_length_limits(entries: &mut [Entry], config: &Config) -> Result<()> {
+    let from_file = match &config.length_limits_path {
+        Some(path) => load_length_limits(Path::new(path))?,
+        None => BTreeMap::new(),
+    };
+    let compile = |limits: &BTreeMap<String, usize>| -> Vec<(Regex, usize)> {
+        limits
+            .iter()
+            .filter_map(|(pattern, &max)| Some((config::key_pattern(pattern)?, max)))
+            .collect()
+    };
+    let (from_file, from_config) = (compile(&from_file), compile(&config.length_limits));
+    for entry in entries {
+        let smallest = |limits: &[(Regex, usize)]| {
+            limits
+                .iter()
+                .filter(|(pattern, _)| pattern.is_match(&entry.key))
+                .map(|&(_, max)| max)
+                .min()
+        };
+        entry.max_length = smallest(&from_file).or_else(|| smallest(&from_config));
+    }
+    Ok(())
+}
+
+fn load_length_limits(path: &Path) -> Result<BTreeMap<String, usize>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("length limits {}", path.display()))?;
+    let limits = if path.extension().unwrap_or_default() == "json" {
+        serde_json::from_str(&content)?
+    } else {
+        toml::from_str(&content)?
+    };
+    Ok(limits)
+}
+
 pub fn save_translated_keys(path: &Path, entries: &[Entry]) -> Result<()> {
-    let translated: Vec<String> = entries
+    let keys_with = |status: EntryStatus| -> Vec<String> {
+        entries
+            .iter()
+            .filter(|e| e.status == status)
+            .map(|e| e.key.clone())
+            .collect()
+    };
+
+    let modified = entries
         .iter()
-        .filter(|e| e.is_translated)
-        .map(|e| e.key.clone())
+        .filter_map(|e| e.modified.map(|time| (e.key.clone(), time.to_rfc3339())))
         .collect();
 
     let data = TranslatedKeysData {
-        keys: translated,
+        keys: keys_with(EntryStatus::Translated),
+        review: keys_with(EntryStatus::NeedsReview),
+        draft: keys_with(EntryStatus::Draft),
+        locked: entries
+            .iter()
+            .filter(|e| e.lock == Lock::Manual)
+            .map(|e| e.key.clone())
+            .collect(),
         last_updated: Local::now().to_rfc3339(),
+        modified,
     };
 
     let content = toml::to_string(&data)?;
@@ -71,52 +275,138 @@ pub fn save_translated_keys(path: &Path, entries: &[Entry]) -> Result<()> {
     Ok(())
 }
 
-pub fn save_translated_json(
-    state: &EditingState,
+// XLIFF de troca com tradutores externos: `<pasta>/<nome>_<sufixo>.xlf`
+pub fn xliff_path(
+    original_path: &Path,
     translations_folder: &str,
     translation_suffix: &str,
-) -> Result<()> {
-    let mut translated_map = Map::new();
-    for entry in &state.entries {
-        translated_map.insert(entry.key.clone(), entry.translated.clone());
-    }
-
-    fs::create_dir_all(translations_folder)?;
+) -> PathBuf {
+    translated_path(
+        original_path,
+        translations_folder,
+        translation_suffix,
+        "xlf",
+    )
+}
 
-    let new_filename = format!(
-        "{}_{}.json",
-        state.original_path.file_stem().unwrap().to_str().unwrap(),
-        translation_suffix
+pub fn export_xliff(state: &EditingState, path: &Path, config: &Config) -> Result<()> {
+    let original = state
+        .original_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+    let languages = Languages {
+        source: &config.source_language,
+        target: &config.target_language,
+    };
+    let content = xliff::export(
+        &state.entries,
+        &original,
+        languages,
+        Version::parse(&config.xliff_version),
     );
-    let new_path = Path::new(translations_folder).join(new_filename);
-
-    let json = serde_json::to_string_pretty(&translated_map)?;
-    fs::write(&new_path, json)?;
-
-    let toml_path = state.original_path.with_extension("toml");
-    save_translated_keys(&toml_path, &state.entries)?;
 
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
     Ok(())
 }
 
-pub fn load_existing_translations(
+pub fn load_xliff(path: &Path) -> Result<Vec<XliffUnit>> {
+    xliff::import(&fs::read_to_string(path)?)
+}
+
+pub fn spreadsheet_path(
     original_path: &Path,
     translations_folder: &str,
     translation_suffix: &str,
-) -> Result<Map<String, Value>> {
-    let translated_filename = format!(
-        "{}_{}.json",
-        original_path.file_stem().unwrap().to_str().unwrap(),
-        translation_suffix
-    );
-    let translated_path = Path::new(translations_folder).join(translated_filename);
+    config: &Config,
+) -> PathBuf {
+    let extension = if config.spreadsheet_format.eq_ignore_ascii_case("tsv") {
+        "tsv"
+    } else {
+        "csv"
+    };
+    translated_path(
+        original_path,
+        translations_folder,
+        translation_suffix,
+        extension,
+    )
+}
 
-    if translated_path.exists() {
-        let content = fs::read_to_string(&translated_path)?;
-        if let Ok(Value::Object(map)) = serde_json::from_str(&content) {
-            return Ok(map);
+pub fn export_spreadsheet(state: &EditingState, path: &Path, config: &Config) -> Result<()> {
+    let content = spreadsheet::export(
+        &state.entries,
+        spreadsheet::delimiter(path),
+        config.spreadsheet_bom,
+    )?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+pub fn load_spreadsheet(path: &Path) -> Result<Vec<SheetRow>> {
+    spreadsheet::import(&fs::read_to_string(path)?, spreadsheet::delimiter(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn copy_dir(from: &Path, to: &Path) {
+        fs::create_dir_all(to).unwrap();
+        for entry in fs::read_dir(from).unwrap() {
+            let path = entry.unwrap().path();
+            let target = to.join(path.file_name().unwrap());
+            if path.is_dir() {
+                copy_dir(&path, &target);
+            } else {
+                fs::copy(&path, &target).unwrap();
+            }
         }
     }
 
-    Ok(Map::new())
+    #[test]
+    fn sources_in_subfolders_are_found_with_their_own_dir() {
+        let dir = std::env::temp_dir().join("transtui-nested");
+        let _ = fs::remove_dir_all(&dir);
+        copy_dir(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/nested"),
+            &dir,
+        );
+        for skipped in ["node_modules/pkg", ".git", "target"] {
+            fs::create_dir_all(dir.join(skipped)).unwrap();
+            fs::write(dir.join(skipped).join("en.json"), "{\"a\": \"b\"}").unwrap();
+        }
+
+        let config = Config {
+            output_template: Some("{dir}/{stem}.{lang}.{ext}".to_string()),
+            ..Config::default()
+        };
+        let context = FormatContext {
+            translations_folder: "out",
+            translation_suffix: "pt",
+            config: &config,
+        };
+        let files = source_files(&dir, &context).unwrap();
+        let found: Vec<_> = files
+            .iter()
+            .map(|path| path.strip_prefix(&dir).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            found,
+            [
+                PathBuf::from("locales/en/app.json"),
+                PathBuf::from("res/values/strings.xml")
+            ]
+        );
+        assert_eq!(
+            output_path(&files[0], &context, "json"),
+            dir.join("locales/en/app.pt.json")
+        );
+    }
 }
@@ -0,0 +1,130 @@
+use regex::Regex;
+use serde_json::Value;
+
+use crate::app::Entry;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ReplaceField {
+    Find,
+    Replace,
+}
+
+// Trecho de texto de uma prévia: `changed` marca a parte afetada pela substituição.
+pub struct DiffSegment {
+    pub text: String,
+    pub changed: bool,
+}
+
+pub struct ReplaceMatch {
+    pub entry_index: usize,
+    pub before: Vec<DiffSegment>,
+    pub after: Vec<DiffSegment>,
+    pub result: String,
+    pub accepted: bool,
+}
+
+pub struct ReplaceState {
+    pub find: String,
+    pub replace: String,
+    pub field: ReplaceField,
+    pub preview: bool,
+    pub matches: Vec<ReplaceMatch>,
+    pub selection: usize,
+    pub error: Option<String>,
+}
+
+impl ReplaceState {
+    pub fn new() -> Self {
+        Self {
+            find: String::new(),
+            replace: String::new(),
+            field: ReplaceField::Find,
+            preview: false,
+            matches: Vec::new(),
+            selection: 0,
+            error: None,
+        }
+    }
+
+    pub fn active_input(&mut self) -> &mut String {
+        match self.field {
+            ReplaceField::Find => &mut self.find,
+            ReplaceField::Replace => &mut self.replace,
+        }
+    }
+}
+
+// Monta a prévia da substituição sobre os valores traduzidos.
+// O padrão é uma regex e a substituição aceita grupos de captura (`$1`, `${nome}`).
+pub fn build_preview(
+    entries: &[Entry],
+    pattern: &str,
+    replacement: &str,
+) -> Result<Vec<ReplaceMatch>, regex::Error> {
+    let regex = Regex::new(pattern)?;
+    let mut matches = Vec::new();
+
+    for (entry_index, entry) in entries.iter().enumerate() {
+        let Value::String(text) = &entry.translated else {
+            continue;
+        };
+        if entry.is_locked() {
+            continue;
+        }
+
+        let mut before = Vec::new();
+        let mut after = Vec::new();
+        let mut result = String::new();
+        let mut last = 0;
+
+        for caps in regex.captures_iter(text) {
+            let whole = caps.get(0).unwrap();
+            if whole.as_str().is_empty() {
+                continue;
+            }
+
+            let unchanged = &text[last..whole.start()];
+            push_segment(&mut before, unchanged, false);
+            push_segment(&mut after, unchanged, false);
+            result.push_str(unchanged);
+
+            let mut expanded = String::new();
+            caps.expand(replacement, &mut expanded);
+            push_segment(&mut before, whole.as_str(), true);
+            push_segment(&mut after, &expanded, true);
+            result.push_str(&expanded);
+
+            last = whole.end();
+        }
+
+        if last == 0 {
+            continue;
+        }
+
+        let rest = &text[last..];
+        push_segment(&mut before, rest, false);
+        push_segment(&mut after, rest, false);
+        result.push_str(rest);
+
+        if result != *text {
+            matches.push(ReplaceMatch {
+                entry_index,
+                before,
+                after,
+                result,
+                accepted: true,
+            });
+        }
+    }
+
+    Ok(matches)
+}
+
+fn push_segment(segments: &mut Vec<DiffSegment>, text: &str, changed: bool) {
+    if !text.is_empty() {
+        segments.push(DiffSegment {
+            text: text.to_string(),
+            changed,
+        });
+    }
+}
@@ -0,0 +1,212 @@
+use anyhow::{Result, anyhow};
+use quick_xml::{
+    escape::{partial_escape, unescape},
+    events::Event,
+    reader::Reader,
+};
+use std::ops::Range;
+
+use super::resource::{PluralItem, ResourceFile, ResourceKind, Slot, plural_slots, whole_lines};
+use super::{attribute, plurals};
+
+// Lê o `strings.xml` do Android. `<plurals>` gera uma entrada por quantidade
+// do idioma de destino (`nome [one]`) e `<string-array>` uma por item (`nome [0]`).
+pub fn parse(content: String, language: &str) -> Result<ResourceFile> {
+    let mut slots = Vec::new();
+    let mut removed = Vec::new();
+    let mut notes: Vec<String> = Vec::new();
+
+    let mut reader = Reader::from_str(&content);
+    loop {
+        let start = reader.buffer_position() as usize;
+        match reader.read_event()? {
+            Event::Comment(comment) => {
+                notes.push(String::from_utf8_lossy(&comment).trim().to_string());
+            }
+            Event::Start(element) => {
+                let tag = element.name().as_ref().to_vec();
+                if !matches!(tag.as_slice(), b"string" | b"plurals" | b"string-array") {
+                    continue;
+                }
+                let name = attribute(&element, "name")?
+                    .ok_or_else(|| anyhow!("<{}> without name", String::from_utf8_lossy(&tag)))?;
+
+                if attribute(&element, "translatable")?.as_deref() == Some("false") {
+                    reader.read_to_end(element.name())?;
+                    removed.push(whole_lines(
+                        &content,
+                        start..reader.buffer_position() as usize,
+                    ));
+                    notes.clear();
+                    continue;
+                }
+
+                let element_notes = std::mem::take(&mut notes);
+                if tag == b"string" {
+                    let span = reader.read_to_end(element.name())?;
+                    slots.push(slot(
+                        &content,
+                        name,
+                        span.start as usize..span.end as usize,
+                        element_notes,
+                    ));
+                    continue;
+                }
+
+                let mut index = 0;
+                let mut items = Vec::new();
+                loop {
+                    let item_start = reader.buffer_position() as usize;
+                    match reader.read_event()? {
+                        Event::Start(item) if item.name().as_ref() == b"item" => {
+                            let quantity = attribute(&item, "quantity")?;
+                            let form = quantity.clone().unwrap_or_else(|| index.to_string());
+                            index += 1;
+                            let span = reader.read_to_end(item.name())?;
+                            let slot = slot(
+                                &content,
+                                format!("{} [{}]", name, form),
+                                span.start as usize..span.end as usize,
+                                element_notes.clone(),
+                            );
+                            match quantity {
+                                Some(category) if tag == b"plurals" => items.push(PluralItem {
+                                    category,
+                                    range: item_start..reader.buffer_position() as usize,
+                                    slot,
+                                }),
+                                _ => slots.push(slot),
+                            }
+                        }
+                        Event::End(end) if end.name().as_ref() == tag.as_slice() => break,
+                        Event::Eof => return Err(anyhow!("unclosed <{}>", name)),
+                        _ => {}
+                    }
+                }
+                plural_slots(
+                    &content,
+                    items,
+                    plurals::categories(language),
+                    |category| format!("{} [{}]", name, category),
+                    |category, indent| {
+                        (
+                            format!("{}<item quantity=\"{}\">", indent, category),
+                            "</item>".to_string(),
+                        )
+                    },
+                    &mut slots,
+                    &mut removed,
+                );
+            }
+            Event::Empty(element)
+                if attribute(&element, "translatable")?.as_deref() == Some("false") =>
+            {
+                removed.push(whole_lines(
+                    &content,
+                    start..reader.buffer_position() as usize,
+                ));
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(ResourceFile::new(
+        ResourceKind::Android,
+        content,
+        slots,
+        removed,
+    ))
+}
+
+fn slot(content: &str, key: String, range: Range<usize>, notes: Vec<String>) -> Slot {
+    let raw = &content[range.clone()];
+    let markup = raw.contains('<');
+    Slot {
+        key,
+        range,
+        value: decode(raw),
+        notes,
+        markup,
+        insert: None,
+    }
+}
+
+// Divide o conteúdo em texto e tags, que passam sem alteração
+fn segments(raw: &str, markup: bool) -> Vec<(bool, &str)> {
+    if !markup {
+        return vec![(false, raw)];
+    }
+    let mut parts = Vec::new();
+    let mut rest = raw;
+    while let Some(open) = rest.find('<') {
+        let close = rest[open..].find('>').map_or(rest.len(), |i| open + i + 1);
+        parts.push((false, &rest[..open]));
+        parts.push((true, &rest[open..close]));
+        rest = &rest[close..];
+    }
+    parts.push((false, rest));
+    parts
+}
+
+fn decode(raw: &str) -> String {
+    let raw = match raw.strip_prefix('"').and_then(|r| r.strip_suffix('"')) {
+        Some(inner) if !inner.ends_with('\\') => inner,
+        _ => raw,
+    };
+
+    let mut value = String::new();
+    for (tag, part) in segments(raw, raw.contains('<')) {
+        if tag {
+            value.push_str(part);
+            continue;
+        }
+        let text = unescape(part).map_or_else(|_| part.to_string(), |t| t.to_string());
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                value.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    if let Some(c) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                        value.push(c);
+                    }
+                }
+                Some(other) => value.push(other),
+                None => value.push('\\'),
+            }
+        }
+    }
+    value
+}
+
+pub fn encode(text: &str, markup: bool) -> String {
+    let mut encoded = String::new();
+    if text.starts_with(['@', '?']) {
+        encoded.push('\\');
+    }
+    for (tag, part) in segments(text, markup) {
+        if tag {
+            encoded.push_str(part);
+            continue;
+        }
+        let mut escaped = String::with_capacity(part.len());
+        for c in part.chars() {
+            match c {
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\t' => escaped.push_str("\\t"),
+                '\'' => escaped.push_str("\\'"),
+                '"' => escaped.push_str("\\\""),
+                other => escaped.push(other),
+            }
+        }
+        encoded.push_str(&partial_escape(escaped.as_str()));
+    }
+    encoded
+}
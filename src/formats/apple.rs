@@ -0,0 +1,254 @@
+use anyhow::{Result, bail};
+use quick_xml::{
+    escape::{partial_escape, unescape},
+    events::Event,
+    reader::Reader,
+};
+
+use super::plurals;
+use super::resource::{PluralItem, ResourceFile, ResourceKind, Slot, plural_slots};
+
+const PLURAL_CATEGORIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+struct Scanner<'a> {
+    content: &'a str,
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn rest(&self) -> &'a str {
+        &self.content[self.position..]
+    }
+
+    fn line(&self) -> usize {
+        self.content[..self.position].lines().count().max(1)
+    }
+
+    // Pula espaços e comentários, guardando o texto dos comentários
+    fn skip_blank(&mut self, notes: &mut Vec<String>) {
+        loop {
+            let trimmed = self.rest().trim_start();
+            self.position = self.content.len() - trimmed.len();
+            if let Some(comment) = trimmed.strip_prefix("/*") {
+                let end = comment.find("*/").unwrap_or(comment.len());
+                notes.push(comment[..end].trim().to_string());
+                self.position += 2 + (end + 2).min(comment.len());
+            } else if let Some(comment) = trimmed.strip_prefix("//") {
+                let end = comment.find('\n').unwrap_or(comment.len());
+                notes.push(comment[..end].trim().to_string());
+                self.position += 2 + end;
+            } else {
+                return;
+            }
+        }
+    }
+
+    // Lê uma string entre aspas e retorna o valor e o intervalo do conteúdo
+    fn quoted(&mut self) -> Result<(String, std::ops::Range<usize>)> {
+        let start = self.position + 1;
+        let mut value = String::new();
+        let mut chars = self.content[start..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.position = start + i + 1;
+                    return Ok((value, start..start + i));
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('r') => value.push('\r'),
+                    Some('U') | Some('u') => {
+                        let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                        if let Some(c) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)
+                        {
+                            value.push(c);
+                        }
+                    }
+                    Some(other) => value.push(other),
+                    None => break,
+                },
+                other => value.push(other),
+            }
+        }
+        bail!("unterminated string at line {}", self.line())
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<()> {
+        if !self.rest().starts_with(symbol) {
+            bail!("expected '{}' at line {}", symbol, self.line());
+        }
+        self.position += symbol.len_utf8();
+        Ok(())
+    }
+}
+
+// Lê um `Localizable.strings` (`"chave" = "valor";`). Comentários antes do par
+// viram notas da entrada.
+pub fn parse_strings(content: String) -> Result<ResourceFile> {
+    let mut slots = Vec::new();
+    let mut notes = Vec::new();
+    let mut scanner = Scanner {
+        content: &content,
+        position: 0,
+    };
+
+    loop {
+        scanner.skip_blank(&mut notes);
+        if scanner.rest().is_empty() {
+            break;
+        }
+        let key = if scanner.rest().starts_with('"') {
+            scanner.quoted()?.0
+        } else {
+            let rest = scanner.rest();
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '=')
+                .unwrap_or(rest.len());
+            let key = rest[..end].to_string();
+            scanner.position += end;
+            key
+        };
+        scanner.skip_blank(&mut Vec::new());
+        scanner.expect('=')?;
+        scanner.skip_blank(&mut Vec::new());
+        if !scanner.rest().starts_with('"') {
+            bail!("expected '\"' at line {}", scanner.line());
+        }
+        let (value, range) = scanner.quoted()?;
+        scanner.skip_blank(&mut Vec::new());
+        scanner.expect(';')?;
+
+        slots.push(Slot {
+            key,
+            range,
+            value,
+            notes: std::mem::take(&mut notes),
+            markup: false,
+            insert: None,
+        });
+    }
+
+    Ok(ResourceFile::new(
+        ResourceKind::Strings,
+        content,
+        slots,
+        Vec::new(),
+    ))
+}
+
+// Lê um `.stringsdict`. Para cada chave, o formato (`NSStringLocalizedFormatKey`)
+// vira a entrada `chave` e cada forma do plural do idioma de destino vira
+// `chave [variável.forma]`.
+pub fn parse_stringsdict(content: String, language: &str) -> Result<ResourceFile> {
+    let mut slots = Vec::new();
+    let mut removed = Vec::new();
+    let mut notes: Vec<String> = Vec::new();
+    // Chave que abriu cada `<dict>` aninhado: raiz, mensagem, variável
+    let mut dicts: Vec<String> = Vec::new();
+    let mut last_key = String::new();
+    let mut key_start = 0;
+    // Formas da variável em leitura
+    let mut items: Vec<PluralItem> = Vec::new();
+
+    let mut reader = Reader::from_str(&content);
+    loop {
+        let start = reader.buffer_position() as usize;
+        match reader.read_event()? {
+            Event::Comment(comment) => {
+                notes.push(String::from_utf8_lossy(&comment).trim().to_string());
+            }
+            Event::Start(element) => match element.name().as_ref() {
+                b"dict" => dicts.push(std::mem::take(&mut last_key)),
+                b"key" => {
+                    key_start = start;
+                    last_key = reader.read_text(element.name())?.to_string();
+                }
+                b"string" => {
+                    let span = reader.read_to_end(element.name())?;
+                    let range = span.start as usize..span.end as usize;
+                    let raw = &content[range.clone()];
+                    let mut slot = |key| Slot {
+                        key,
+                        range: range.clone(),
+                        value: unescape(raw).map_or_else(|_| raw.to_string(), |v| v.to_string()),
+                        notes: std::mem::take(&mut notes),
+                        markup: false,
+                        insert: None,
+                    };
+                    match dicts.as_slice() {
+                        [_, message] if last_key == "NSStringLocalizedFormatKey" => {
+                            slots.push(slot(message.clone()));
+                        }
+                        [_, message, variable]
+                            if PLURAL_CATEGORIES.contains(&last_key.as_str()) =>
+                        {
+                            let key = format!("{} [{}.{}]", message, variable, last_key);
+                            items.push(PluralItem {
+                                category: last_key.clone(),
+                                range: key_start..reader.buffer_position() as usize,
+                                slot: slot(key),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            },
+            Event::End(element) if element.name().as_ref() == b"dict" => {
+                if let [_, message, variable] = dicts.as_slice() {
+                    // `zero` vale em qualquer idioma no iOS; só não é criada
+                    let mut categories = plurals::categories(language).to_vec();
+                    if items.iter().any(|item| item.category == "zero")
+                        && !categories.contains(&"zero")
+                    {
+                        categories.insert(0, "zero");
+                    }
+                    plural_slots(
+                        &content,
+                        std::mem::take(&mut items),
+                        &categories,
+                        |category| format!("{} [{}.{}]", message, variable, category),
+                        |category, indent| {
+                            (
+                                format!("{0}<key>{1}</key>\n{0}<string>", indent, category),
+                                "</string>".to_string(),
+                            )
+                        },
+                        &mut slots,
+                        &mut removed,
+                    );
+                }
+                dicts.pop();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(ResourceFile::new(
+        ResourceKind::StringsDict,
+        content,
+        slots,
+        removed,
+    ))
+}
+
+pub fn encode_strings(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+pub fn encode_plist(text: &str) -> String {
+    partial_escape(text).to_string()
+}
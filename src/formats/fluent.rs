@@ -0,0 +1,204 @@
+use anyhow::Result;
+use regex::Regex;
+use std::sync::LazyLock;
+
+use super::resource::{ResourceFile, ResourceKind, Slot};
+
+// `id =` de mensagens e termos (`-marca =`) e `.atributo =`
+static ENTRY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(-?[a-zA-Z][\w-]*)[ \t]*=[ \t]*").unwrap());
+static ATTRIBUTE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[ \t]+\.([a-zA-Z][\w-]*)[ \t]*=[ \t]*").unwrap());
+// Variante de uma expressão de seleção: `[one] texto` ou `*[other] texto`
+static VARIANT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[ \t]+\*?\[[ \t]*([\w-]+)[ \t]*\][ \t]*").unwrap());
+
+// Recuo usado nas linhas de continuação de textos com várias linhas
+const INDENT: &str = "    ";
+
+struct Line<'a> {
+    start: usize,
+    text: &'a str,
+}
+
+// Lê um arquivo Fluent. Mensagens e termos viram `id`, atributos `id.atributo`,
+// e cada variante de uma seleção que ocupa o valor inteiro vira `id [variante]`.
+pub fn parse(content: String) -> Result<ResourceFile> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for text in content.split('\n') {
+        lines.push(Line {
+            start,
+            text: text.trim_end_matches('\r'),
+        });
+        start += text.len() + 1;
+    }
+
+    let mut slots = Vec::new();
+    let mut notes: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = &lines[i];
+        if line.text.trim().is_empty() {
+            notes.clear();
+            i += 1;
+            continue;
+        }
+        if let Some(comment) = line.text.strip_prefix('#') {
+            // Comentários de grupo (`##`) e de arquivo (`###`) não são da mensagem seguinte
+            if comment.starts_with('#') {
+                notes.clear();
+            } else {
+                notes.push(comment.trim().to_string());
+            }
+            i += 1;
+            continue;
+        }
+
+        let Some(found) = ENTRY.captures(line.text) else {
+            i += 1;
+            continue;
+        };
+        let id = found[1].to_string();
+        let inline = found[0].len();
+        let entry_notes = std::mem::take(&mut notes);
+
+        i = pattern(&lines, i, inline, &id, &entry_notes, &mut slots);
+        while i < lines.len() {
+            let Some(attribute) = ATTRIBUTE.captures(lines[i].text) else {
+                break;
+            };
+            let key = format!("{}.{}", id, &attribute[1]);
+            let inline = attribute[0].len();
+            i = pattern(&lines, i, inline, &key, &entry_notes, &mut slots);
+        }
+    }
+
+    Ok(ResourceFile::new(
+        ResourceKind::Fluent,
+        content,
+        slots,
+        Vec::new(),
+    ))
+}
+
+// Lê o valor que começa na linha `first`, coluna `inline`, com as linhas de
+// continuação (recuadas, sem começar com `.`). Retorna a próxima linha.
+fn pattern(
+    lines: &[Line],
+    first: usize,
+    inline: usize,
+    key: &str,
+    notes: &[String],
+    slots: &mut Vec<Slot>,
+) -> usize {
+    let mut end = first + 1;
+    while end < lines.len() {
+        let text = lines[end].text;
+        let continued = text.trim().is_empty()
+            || (text.starts_with([' ', '\t']) && !text.trim_start().starts_with('.'));
+        if !continued {
+            break;
+        }
+        end += 1;
+    }
+    while end > first + 1 && lines[end - 1].text.trim().is_empty() {
+        end -= 1;
+    }
+
+    // Partes do valor: (posição inicial no arquivo, texto)
+    let mut parts: Vec<(usize, &str)> = Vec::new();
+    let head = &lines[first].text[inline..];
+    if !head.trim().is_empty() {
+        parts.push((lines[first].start + inline, head));
+    }
+    for line in &lines[first + 1..end] {
+        parts.push((line.start, line.text));
+    }
+    let Some(&(_, opening)) = parts.iter().find(|(_, text)| !text.trim().is_empty()) else {
+        return end;
+    };
+
+    let opening = opening.trim();
+    if opening.starts_with('{') && opening.ends_with("->") {
+        let body = &lines[first + 1..end];
+        for (n, line) in body.iter().enumerate() {
+            let Some(variant) = VARIANT.captures(line.text) else {
+                continue;
+            };
+            // O texto da variante segue até a próxima variante ou o `}` final
+            let continuation = body[n + 1..]
+                .iter()
+                .take_while(|l| {
+                    let text = l.text.trim();
+                    !text.is_empty() && !text.starts_with('}') && !VARIANT.is_match(l.text)
+                })
+                .count();
+            let text_start = variant[0].len();
+            let mut parts = vec![(line.start + text_start, &line.text[text_start..])];
+            parts.extend(
+                body[n + 1..n + 1 + continuation]
+                    .iter()
+                    .map(|l| (l.start, l.text)),
+            );
+            slots.push(text_slot(
+                format!("{} [{}]", key, &variant[1]),
+                &parts,
+                notes,
+            ));
+        }
+        return end;
+    }
+
+    let first_part = parts
+        .iter()
+        .position(|(_, text)| !text.trim().is_empty())
+        .unwrap_or(0);
+    slots.push(text_slot(key.to_string(), &parts[first_part..], notes));
+    end
+}
+
+// Texto com várias linhas: o valor começa no primeiro trecho e as linhas
+// seguintes perdem o recuo comum
+fn text_slot(key: String, parts: &[(usize, &str)], notes: &[String]) -> Slot {
+    let (start, first_text) = parts[0];
+    let trimmed = first_text.trim_start();
+    let range_start = start + (first_text.len() - trimmed.len());
+    let rest = &parts[1..];
+    let indent = rest
+        .iter()
+        .filter(|(_, text)| !text.trim().is_empty())
+        .map(|(_, text)| text.len() - text.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    let mut value = trimmed.to_string();
+    for (_, text) in rest {
+        value.push('\n');
+        value.push_str(text.get(indent..).unwrap_or(""));
+    }
+    let (last_start, last_text) = parts[parts.len() - 1];
+
+    Slot {
+        key,
+        range: range_start..last_start + last_text.len(),
+        value,
+        notes: notes.to_vec(),
+        markup: false,
+        insert: None,
+    }
+}
+
+pub fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for (n, line) in text.split('\n').enumerate() {
+        if n > 0 {
+            encoded.push('\n');
+            if !line.is_empty() {
+                encoded.push_str(INDENT);
+            }
+        }
+        encoded.push_str(line);
+    }
+    encoded
+}
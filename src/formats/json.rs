@@ -0,0 +1,450 @@
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{Map, Value, ser::PrettyFormatter};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::output_value;
+use super::registry::{Document, Format, FormatContext};
+use crate::app::{Entry, EntryStatus, Lock};
+use crate::config::Config;
+use crate::file_operations;
+
+// JSON de origem aberto; a tradução vai para `output` no mesmo dialeto
+pub struct JsonDocument {
+    dialect: JsonDialect,
+    source: Map<String, Value>,
+    style: JsonStyle,
+    output: PathBuf,
+}
+
+pub struct JsonFormat;
+
+impl Format for JsonFormat {
+    fn name(&self) -> &'static str {
+        "JSON, ARB"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["json", "arb"]
+    }
+
+    fn output_path(&self, path: &Path, context: &FormatContext) -> PathBuf {
+        let extension = path.extension().unwrap_or_default().to_string_lossy();
+        file_operations::output_path(path, context, &extension)
+    }
+
+    // Reconhece os dialetos ARB e Chrome. Retorna `None` se o arquivo não for
+    // um objeto.
+    fn load(&self, path: &Path, context: &FormatContext) -> Result<Option<(Vec<Entry>, Document)>> {
+        let content = fs::read_to_string(path)?;
+        let Value::Object(source) = serde_json::from_str(&content)? else {
+            return Ok(None);
+        };
+
+        let dialect = detect(path, &source);
+        let style = JsonStyle::detect(&content);
+        let output = self.output_path(path, context);
+        let mut entries = to_entries(&source, dialect, &existing_translations(&output)?);
+        file_operations::apply_sidecar(&mut entries, path, context.config)?;
+
+        let document = JsonDocument {
+            dialect,
+            source,
+            style,
+            output,
+        };
+        Ok(Some((entries, Document::new(document))))
+    }
+
+    fn save(
+        &self,
+        document: &mut Document,
+        entries: &[Entry],
+        context: &FormatContext,
+    ) -> Result<()> {
+        let config = context.config;
+        let JsonDocument {
+            dialect,
+            source,
+            style,
+            output,
+        } = document.get_mut(self.name())?;
+        // `entries` mantém a ordem do arquivo de origem, independente da ordenação da tabela
+        let translated_map = to_output(source, *dialect, entries, config);
+
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let previous = if output.exists() {
+            Some(fs::read_to_string(&output)?)
+        } else {
+            None
+        };
+        let previous_map = previous
+            .as_deref()
+            .and_then(|content| serde_json::from_str(content).ok())
+            .and_then(|value| match value {
+                Value::Object(map) => Some(map),
+                _ => None,
+            });
+        let translated_map = match &previous_map {
+            Some(previous_map) => keep_previous_keys(translated_map, previous_map, source),
+            None => translated_map,
+        };
+
+        // Com `json_keep_output_style` a tradução existente mantém a própria formatação
+        let json = match &previous {
+            Some(content) if config.json_keep_output_style => {
+                JsonStyle::detect(content).render(&translated_map)?
+            }
+            _ => style.render(&translated_map)?,
+        };
+        if previous.as_deref() != Some(json.as_str()) {
+            fs::write(&output, json)?;
+        }
+        Ok(())
+    }
+}
+
+// Mensagens da tradução já gravada, se existir
+fn existing_translations(output: &Path) -> Result<Map<String, Value>> {
+    if output.exists() {
+        let content = fs::read_to_string(output)?;
+        if let Ok(Value::Object(map)) = serde_json::from_str(&content) {
+            let dialect = detect(output, &map);
+            return Ok(messages(&map, dialect));
+        }
+    }
+    Ok(Map::new())
+}
+
+// Variantes de JSON com metadados junto das mensagens
+#[derive(Clone, Copy, PartialEq)]
+pub enum JsonDialect {
+    Plain,
+    // Flutter ARB: `"chave": "texto"` com metadados em `"@chave": {...}`
+    Arb,
+    // Extensões do Chrome: `"chave": {"message": "texto", "description": ...}`
+    Chrome,
+}
+
+pub fn detect(path: &Path, map: &Map<String, Value>) -> JsonDialect {
+    if path.extension().unwrap_or_default() == "arb" || map.keys().any(|k| k.starts_with('@')) {
+        JsonDialect::Arb
+    } else if !map.is_empty()
+        && map
+            .values()
+            .all(|v| v.get("message").is_some_and(Value::is_string))
+    {
+        JsonDialect::Chrome
+    } else {
+        JsonDialect::Plain
+    }
+}
+
+// Valores editáveis do arquivo: só a mensagem, sem os metadados do dialeto
+pub fn messages(map: &Map<String, Value>, dialect: JsonDialect) -> Map<String, Value> {
+    map.iter()
+        .filter_map(|(key, value)| match dialect {
+            JsonDialect::Plain => Some((key.clone(), value.clone())),
+            JsonDialect::Arb if key.starts_with('@') => None,
+            JsonDialect::Arb => Some((key.clone(), value.clone())),
+            JsonDialect::Chrome => value
+                .get("message")
+                .map(|message| (key.clone(), message.clone())),
+        })
+        .collect()
+}
+
+pub fn to_entries(
+    map: &Map<String, Value>,
+    dialect: JsonDialect,
+    existing: &Map<String, Value>,
+) -> Vec<Entry> {
+    messages(map, dialect)
+        .into_iter()
+        .map(|(key, original)| {
+            let translated = existing.get(&key).cloned().unwrap_or(Value::Null);
+            let notes = match dialect {
+                JsonDialect::Plain => Vec::new(),
+                JsonDialect::Arb => map
+                    .get(&format!("@{}", key))
+                    .map(|metadata| metadata_notes(metadata, "{", "}"))
+                    .unwrap_or_default(),
+                JsonDialect::Chrome => map
+                    .get(&key)
+                    .map(|metadata| metadata_notes(metadata, "$", "$"))
+                    .unwrap_or_default(),
+            };
+            Entry {
+                key,
+                original,
+                translated,
+                status: EntryStatus::Untranslated,
+                lock: Lock::Unlocked,
+                max_length: None,
+                dirty: false,
+                modified: None,
+                notes,
+            }
+        })
+        .collect()
+}
+
+// Descrição e placeholders como contexto somente leitura. Os placeholders
+// aparecem como são escritos na mensagem (`{nome}` no ARB, `$nome$` no Chrome).
+fn metadata_notes(metadata: &Value, open: &str, close: &str) -> Vec<String> {
+    let mut notes = Vec::new();
+    if let Some(description) = metadata.get("description").and_then(Value::as_str) {
+        notes.push(description.to_string());
+    }
+    if let Some(Value::Object(placeholders)) = metadata.get("placeholders") {
+        for (name, details) in placeholders {
+            let details: Vec<String> = details
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter_map(|(field, value)| {
+                    let text = match value {
+                        Value::String(text) => text.clone(),
+                        Value::Object(_) | Value::Array(_) => return None,
+                        other => other.to_string(),
+                    };
+                    Some(format!("{}={}", field, text))
+                })
+                .collect();
+            notes.push(format!("{}{}{}: {}", open, name, close, details.join(", ")));
+        }
+    }
+    notes
+}
+
+// Monta o arquivo traduzido no mesmo dialeto do original. No ARB os metadados
+// são mantidos e `@@locale` passa a ser o idioma de destino; no Chrome só a
+// `message` de cada objeto é trocada. Chaves omitidas por `untranslated_output`
+// saem junto com os seus metadados.
+pub fn to_output(
+    source: &Map<String, Value>,
+    dialect: JsonDialect,
+    entries: &[Entry],
+    config: &Config,
+) -> Map<String, Value> {
+    let translated: HashMap<&str, Option<Value>> = entries
+        .iter()
+        .map(|entry| (entry.key.as_str(), output_value(entry, config)))
+        .collect();
+
+    let mut output = Map::new();
+    match dialect {
+        JsonDialect::Plain => {
+            for entry in entries {
+                if let Some(Some(value)) = translated.get(entry.key.as_str()) {
+                    output.insert(entry.key.clone(), value.clone());
+                }
+            }
+        }
+        JsonDialect::Arb => {
+            output.insert(
+                "@@locale".to_string(),
+                Value::String(config.target_language.replace('-', "_")),
+            );
+            for (key, value) in source {
+                if key == "@@locale" {
+                    continue;
+                }
+                let message = key.strip_prefix('@').unwrap_or(key);
+                let value = match translated.get(message) {
+                    Some(None) => continue,
+                    Some(Some(text)) if message == key => text.clone(),
+                    _ => value.clone(),
+                };
+                output.insert(key.clone(), value);
+            }
+        }
+        JsonDialect::Chrome => {
+            for (key, value) in source {
+                let mut value = value.clone();
+                match translated.get(key.as_str()) {
+                    Some(None) => continue,
+                    Some(Some(text)) => {
+                        if let Value::Object(object) = &mut value {
+                            object.insert("message".to_string(), text.clone());
+                        }
+                    }
+                    None => {}
+                }
+                output.insert(key.clone(), value);
+            }
+        }
+    }
+    output
+}
+
+// Formatação de um arquivo JSON, reproduzida ao gravar a tradução para que o
+// diff mostre só os textos alterados
+pub struct JsonStyle {
+    // `None` para arquivos numa linha só
+    indent: Option<String>,
+    crlf: bool,
+    trailing_newline: bool,
+    // Caracteres fora do ASCII escritos como `\uXXXX`
+    ascii: bool,
+    uppercase_escapes: bool,
+}
+
+impl JsonStyle {
+    pub fn detect(content: &str) -> Self {
+        let indent = content.lines().skip(1).find_map(|line| {
+            let trimmed = line.trim_start_matches([' ', '\t']);
+            (trimmed.len() < line.len() && !trimmed.is_empty())
+                .then(|| line[..line.len() - trimmed.len()].to_string())
+        });
+        let escape = unicode_escape(content).map(|i| &content[i + 2..]);
+        Self {
+            indent: content
+                .trim_end()
+                .contains('\n')
+                .then(|| indent.unwrap_or_else(|| "  ".to_string())),
+            crlf: content.contains("\r\n"),
+            trailing_newline: content.ends_with('\n'),
+            ascii: content.is_ascii() && escape.is_some(),
+            uppercase_escapes: escape
+                .is_some_and(|hex| hex.chars().take(4).any(|c| c.is_ascii_uppercase())),
+        }
+    }
+
+    pub fn render(&self, map: &Map<String, Value>) -> Result<String> {
+        let mut text = match &self.indent {
+            Some(indent) => {
+                let mut buffer = Vec::new();
+                let formatter = PrettyFormatter::with_indent(indent.as_bytes());
+                let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+                map.serialize(&mut serializer)?;
+                String::from_utf8(buffer)?
+            }
+            None => serde_json::to_string(map)?,
+        };
+        if self.ascii {
+            text = escape_non_ascii(&text, self.uppercase_escapes);
+        }
+        if self.crlf {
+            text = text.replace('\n', "\r\n");
+        }
+        if self.trailing_newline {
+            text.push_str(if self.crlf { "\r\n" } else { "\n" });
+        }
+        Ok(text)
+    }
+}
+
+// Posição do primeiro `\u`, pulando as barras escapadas (`"C:\\users"`)
+fn unicode_escape(content: &str) -> Option<usize> {
+    let mut chars = content.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some((_, 'u')) => return Some(i),
+                _ => continue,
+            }
+        }
+    }
+    None
+}
+
+// Fora das strings o JSON gerado só tem ASCII, então basta trocar cada caractere
+fn escape_non_ascii(text: &str, uppercase: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii() {
+            escaped.push(c);
+            continue;
+        }
+        let mut units = [0u16; 2];
+        for unit in c.encode_utf16(&mut units) {
+            if uppercase {
+                escaped.push_str(&format!("\\u{:04X}", unit));
+            } else {
+                escaped.push_str(&format!("\\u{:04x}", unit));
+            }
+        }
+    }
+    escaped
+}
+
+// Mantém as chaves que só existiam na tradução anterior, cada uma logo após a
+// chave que a precedia naquele arquivo. Chaves da origem omitidas da saída
+// (por `untranslated_output`) não voltam por aqui.
+pub fn keep_previous_keys(
+    output: Map<String, Value>,
+    previous: &Map<String, Value>,
+    source: &Map<String, Value>,
+) -> Map<String, Value> {
+    let mut after: HashMap<Option<String>, Vec<(&String, &Value)>> = HashMap::new();
+    let mut anchor = None;
+    for (key, value) in previous {
+        if output.contains_key(key) {
+            anchor = Some(key.clone());
+        } else if !source.contains_key(key) {
+            after.entry(anchor.clone()).or_default().push((key, value));
+        }
+    }
+    if after.is_empty() {
+        return output;
+    }
+
+    let mut merged = Map::new();
+    let mut insert = |merged: &mut Map<String, Value>, anchor: Option<String>| {
+        for (key, value) in after.remove(&anchor).unwrap_or_default() {
+            merged.insert(key.clone(), value.clone());
+        }
+    };
+    insert(&mut merged, None);
+    for (key, value) in output {
+        let anchor = previous.contains_key(&key).then(|| key.clone());
+        merged.insert(key, value);
+        insert(&mut merged, anchor);
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previous_only_keys_stay_after_their_anchor() {
+        let dir = std::env::temp_dir().join("transtui-json-previous");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/json");
+        let path = dir.join("messages.json");
+        fs::copy(fixtures.join("messages.json"), &path).unwrap();
+        let output = dir.join("messages_pt.json");
+        fs::write(
+            &output,
+            r#"{"title": "Configurações", "old": "Antigo", "save": "Salvar", "cancel": "Cancelar", "last": "Fim"}"#,
+        )
+        .unwrap();
+
+        let config = Config::default();
+        let folder = dir.to_string_lossy().to_string();
+        let context = FormatContext {
+            translations_folder: &folder,
+            translation_suffix: "pt",
+            config: &config,
+        };
+        let (entries, mut document) = JsonFormat.load(&path, &context).unwrap().unwrap();
+        JsonFormat.save(&mut document, &entries, &context).unwrap();
+
+        let saved: Map<String, Value> =
+            serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(
+            saved.keys().collect::<Vec<_>>(),
+            ["title", "old", "save", "cancel", "last"]
+        );
+    }
+}
@@ -0,0 +1,80 @@
+pub mod android;
+pub mod apple;
+pub mod fluent;
+pub mod json;
+pub mod plurals;
+pub mod po;
+pub mod properties;
+pub mod registry;
+pub mod resource;
+pub mod spreadsheet;
+pub mod tmx;
+pub mod xliff;
+pub mod yaml;
+
+use anyhow::Result;
+use quick_xml::events::BytesStart;
+use regex::Regex;
+use serde_json::Value;
+use std::{fs, path::Path, sync::LazyLock};
+
+use crate::app::Entry;
+use crate::config::Config;
+use crate::ui::format_json_value;
+
+// Código de idioma em chaves e nomes de arquivo (`en`, `pt-BR`, `mensagens_en`)
+pub static LOCALE_CODE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-z]{2,3}([-_][A-Za-z]{2,4})?$").unwrap());
+
+// Lê o arquivo considerando o BOM; `.strings` e TMX costumam vir em UTF-16 e
+// `.properties` em ISO-8859-1
+pub fn read(path: &Path) -> Result<String> {
+    let bytes = fs::read(path)?;
+    let utf16 = |bytes: &[u8], read: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| read([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    };
+    Ok(match bytes.as_slice() {
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8(rest.to_vec())?,
+        _ => String::from_utf8(bytes)
+            .unwrap_or_else(|error| error.as_bytes().iter().map(|&b| b as char).collect()),
+    })
+}
+
+// Texto cru do valor, sem o escape que `format_json_value` aplica às strings
+pub fn text_of(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => format_json_value(other),
+    }
+}
+
+// Valor gravado no arquivo traduzido: a tradução, o texto de origem nas
+// entradas bloqueadas, ou o que `untranslated_output` define para as entradas
+// sem tradução. `None` deixa a chave de fora.
+pub fn output_value(entry: &Entry, config: &Config) -> Option<Value> {
+    if entry.is_locked() {
+        return Some(entry.original.clone());
+    }
+    if entry.has_translation() {
+        return Some(entry.translated.clone());
+    }
+    match config.untranslated_output.as_str() {
+        "omit" => None,
+        "empty" => Some(Value::String(String::new())),
+        _ => Some(entry.original.clone()),
+    }
+}
+
+// Valor de um atributo XML, sem escapes
+pub fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>> {
+    match element.try_get_attribute(name)? {
+        Some(attribute) => Ok(Some(attribute.unescape_value()?.to_string())),
+        None => Ok(None),
+    }
+}
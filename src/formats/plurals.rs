@@ -0,0 +1,78 @@
+// Regras de plural por idioma. A região só conta quando tem regra própria
+// (`pt-PT`); fora isso vale a do idioma (`pt-BR` usa `pt`).
+
+const ONE_FORM: &str = "nplurals=1; plural=0;";
+const SINGULAR_ONE: &str = "nplurals=2; plural=(n != 1);";
+const SINGULAR_ZERO_ONE: &str = "nplurals=2; plural=(n > 1);";
+const EAST_SLAVIC: &str = "nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);";
+const POLISH: &str =
+    "nplurals=3; plural=(n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);";
+const WEST_SLAVIC: &str = "nplurals=3; plural=(n==1 ? 0 : n>=2 && n<=4 ? 1 : 2);";
+const ARABIC: &str = "nplurals=6; plural=(n==0 ? 0 : n==1 ? 1 : n==2 ? 2 : n%100>=3 && n%100<=10 ? 3 : n%100>=11 ? 4 : 5);";
+
+// `pt_BR` e `PT-br` viram `pt-br`
+fn normalize(language: &str) -> String {
+    language.to_lowercase().replace('_', "-")
+}
+
+// Procura primeiro o código completo e depois só o idioma
+fn lookup<T: Copy>(language: &str, table: &[(&[&str], T)]) -> Option<T> {
+    let code = normalize(language);
+    let base = code.split('-').next().unwrap_or_default();
+    [code.as_str(), base].into_iter().find_map(|code| {
+        table
+            .iter()
+            .find(|(codes, _)| codes.contains(&code))
+            .map(|&(_, value)| value)
+    })
+}
+
+// `Plural-Forms` do gettext para o idioma, quando conhecido
+pub fn gettext_rule(language: &str) -> Option<&'static str> {
+    lookup(
+        language,
+        &[
+            (&["pt-pt"], SINGULAR_ONE),
+            (&["fr", "pt"], SINGULAR_ZERO_ONE),
+            (
+                &[
+                    "bg", "ca", "da", "de", "el", "en", "es", "et", "fi", "gl", "he", "hu", "it",
+                    "nb", "nl", "nn", "no", "sv", "tr",
+                ],
+                SINGULAR_ONE,
+            ),
+            (&["be", "bs", "hr", "ru", "sr", "uk"], EAST_SLAVIC),
+            (&["pl"], POLISH),
+            (&["cs", "sk"], WEST_SLAVIC),
+            (&["ar"], ARABIC),
+            (
+                &["id", "ja", "km", "ko", "lo", "ms", "my", "th", "vi", "zh"],
+                ONE_FORM,
+            ),
+        ],
+    )
+}
+
+// Categorias de plural do CLDR para o idioma, na ordem do CLDR. Idiomas fora
+// da tabela ficam com `one` e `other`.
+pub fn categories(language: &str) -> &'static [&'static str] {
+    lookup(
+        language,
+        &[
+            (
+                &["id", "ja", "km", "ko", "lo", "ms", "my", "th", "vi", "zh"],
+                &["other"][..],
+            ),
+            (&["ca", "es", "fr", "it", "pt"], &["one", "many", "other"]),
+            (&["he"], &["one", "two", "other"]),
+            (&["bs", "hr", "sr"], &["one", "few", "other"]),
+            (
+                &["be", "cs", "lt", "pl", "ru", "sk", "uk"],
+                &["one", "few", "many", "other"],
+            ),
+            (&["sl"], &["one", "two", "few", "other"]),
+            (&["ar"], &["zero", "one", "two", "few", "many", "other"]),
+        ],
+    )
+    .unwrap_or(&["one", "other"])
+}
@@ -0,0 +1,749 @@
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::registry::{Document, Format, FormatContext};
+use super::{plurals, text_of};
+use crate::app::{Entry, EntryStatus, Lock};
+use crate::file_operations;
+
+// PO aberto, gravado em `output`: o próprio arquivo, ou o `.po` de saída
+// quando a origem é um `.pot`
+pub struct PoDocument {
+    file: PoFile,
+    output: PathBuf,
+}
+
+pub struct PoFormat;
+
+impl Format for PoFormat {
+    fn name(&self) -> &'static str {
+        "PO, POT"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["po", "pot"]
+    }
+
+    // Um `.po` é editado no próprio arquivo; um `.pot` é tratado como origem
+    fn output_path(&self, path: &Path, context: &FormatContext) -> PathBuf {
+        if path.extension().unwrap_or_default() == "pot" {
+            file_operations::output_path(path, context, "po")
+        } else {
+            path.to_path_buf()
+        }
+    }
+
+    // O `.po` gerado a partir de um `.pot` recebe o idioma de destino no
+    // cabeçalho; as traduções vêm do `.po` de saída, se ele já existir
+    fn load(&self, path: &Path, context: &FormatContext) -> Result<Option<(Vec<Entry>, Document)>> {
+        let mut file = parse(&fs::read_to_string(path)?)?;
+        let output = self.output_path(path, context);
+        if output != path {
+            file.set_language(&context.config.target_language);
+            if output.exists() {
+                let existing = parse(&fs::read_to_string(&output)?)?;
+                merge_existing(&mut file, &existing);
+            }
+        }
+
+        let mut entries = to_entries(&mut file);
+        // O estado vem do próprio `.po`; do arquivo lateral só a data de modificação
+        let keys_data = file_operations::load_translated_keys(&path.with_extension("toml"))?;
+        let modified_times = file_operations::parse_modified_times(&keys_data.modified);
+        for entry in &mut entries {
+            entry.modified = modified_times.get(&entry.key).copied();
+        }
+        file_operations::apply_locks(&mut entries, &keys_data, context.config);
+        file_operations::apply_length_limits(&mut entries, context.config)?;
+        Ok(Some((entries, Document::new(PoDocument { file, output }))))
+    }
+
+    fn save(
+        &self,
+        document: &mut Document,
+        entries: &[Entry],
+        _context: &FormatContext,
+    ) -> Result<()> {
+        let PoDocument { file, output } = document.get_mut(self.name())?;
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        save(file, entries, output)
+    }
+}
+
+// Uma mensagem do arquivo PO. As linhas originais são guardadas para que
+// comentários, quebras e campos não editados sejam regravados sem alteração.
+pub struct PoUnit {
+    pub comments: Vec<String>,
+    pub flags: Vec<String>,
+    pub msgctxt: Option<String>,
+    pub msgid: String,
+    pub msgid_plural: Option<String>,
+    pub msgstr: Vec<String>,
+    // Linhas de msgctxt/msgid/msgid_plural como estavam no arquivo
+    source_lines: Vec<String>,
+    // Linhas de cada msgstr como estavam no arquivo
+    msgstr_lines: Vec<Vec<String>>,
+}
+
+impl PoUnit {
+    fn is_header(&self) -> bool {
+        self.msgid.is_empty() && self.msgctxt.is_none()
+    }
+
+    pub fn is_fuzzy(&self) -> bool {
+        self.flags.iter().any(|f| f == "fuzzy")
+    }
+
+    pub fn key(&self) -> String {
+        match &self.msgctxt {
+            Some(ctx) => format!("[{}] {}", ctx, self.msgid),
+            None => self.msgid.clone(),
+        }
+    }
+
+    // Formas do plural: `nplurals` do cabeçalho, ou as que o arquivo já tem
+    fn plural_count(&self, nplurals: Option<usize>) -> usize {
+        match (&self.msgid_plural, nplurals) {
+            (Some(_), Some(n)) => n.max(1),
+            _ => self.msgstr.len().max(1),
+        }
+    }
+
+    fn new() -> Self {
+        Self {
+            comments: Vec::new(),
+            flags: Vec::new(),
+            msgctxt: None,
+            msgid: String::new(),
+            msgid_plural: None,
+            msgstr: Vec::new(),
+            source_lines: Vec::new(),
+            msgstr_lines: Vec::new(),
+        }
+    }
+}
+
+pub struct PoFile {
+    pub units: Vec<PoUnit>,
+    // Linhas soltas no fim do arquivo (por exemplo, mensagens obsoletas `#~`)
+    trailing: Vec<String>,
+    // Para cada entrada da tabela: (mensagem, forma do plural)
+    slots: Vec<(usize, usize)>,
+}
+
+impl PoFile {
+    fn header(&self) -> Option<&PoUnit> {
+        self.units.iter().find(|unit| unit.is_header())
+    }
+
+    // `nplurals` do `Plural-Forms` do cabeçalho. Um `.pot` costuma trazer
+    // `nplurals=INTEGER`, que conta como ausente.
+    pub fn nplurals(&self) -> Option<usize> {
+        let header = self.header()?.msgstr.first()?;
+        let rule = header
+            .lines()
+            .find_map(|line| line.strip_prefix("Plural-Forms:"))?;
+        let count = rule.split_once("nplurals=")?.1;
+        let end = count
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(count.len());
+        count[..end].parse().ok()
+    }
+
+    // Preenche `Language` e, se o idioma for conhecido, `Plural-Forms` no
+    // cabeçalho, criando-o quando o arquivo não tem um
+    pub fn set_language(&mut self, language: &str) {
+        if self.header().is_none() {
+            let mut header = PoUnit::new();
+            header.source_lines.push("msgid \"\"".to_string());
+            header.msgstr.push(String::new());
+            header.msgstr_lines.push(Vec::new());
+            self.units.insert(0, header);
+        }
+        let header = self
+            .units
+            .iter_mut()
+            .find(|unit| unit.is_header())
+            .expect("header inserted above");
+        if header.msgstr.is_empty() {
+            header.msgstr.push(String::new());
+            header.msgstr_lines.push(Vec::new());
+        }
+
+        let mut text = header.msgstr[0].clone();
+        set_header_field(&mut text, "Language", language);
+        if let Some(rule) = plurals::gettext_rule(language) {
+            set_header_field(&mut text, "Plural-Forms", rule);
+        }
+        if text != header.msgstr[0] {
+            header.msgstr[0] = text;
+            header.msgstr_lines[0].clear();
+        }
+    }
+}
+
+// Troca o valor de `Nome: valor` no texto do cabeçalho, ou o acrescenta no fim
+fn set_header_field(header: &mut String, name: &str, value: &str) {
+    let prefix = format!("{}:", name);
+    let field = format!("{} {}\n", prefix, value);
+    let mut found = false;
+    let lines: Vec<String> = header
+        .split_inclusive('\n')
+        .map(|line| {
+            if line.starts_with(&prefix) {
+                found = true;
+                field.clone()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    *header = lines.concat();
+    if !found {
+        if !header.is_empty() && !header.ends_with('\n') {
+            header.push('\n');
+        }
+        header.push_str(&field);
+    }
+}
+
+#[derive(PartialEq)]
+enum Field {
+    None,
+    Context,
+    Id,
+    Plural,
+    Str(usize),
+}
+
+pub fn parse(content: &str) -> Result<PoFile> {
+    let mut units = Vec::new();
+    let mut comments: Vec<String> = Vec::new();
+    let mut unit: Option<PoUnit> = None;
+    let mut field = Field::None;
+
+    let finish = |unit: &mut Option<PoUnit>, units: &mut Vec<PoUnit>| {
+        if let Some(unit) = unit.take() {
+            units.push(unit);
+        }
+    };
+
+    for (number, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            finish(&mut unit, &mut units);
+            field = Field::None;
+            // Linhas em branco entre comentários soltos também são preservadas
+            if !comments.is_empty() {
+                comments.push(String::new());
+            }
+            continue;
+        }
+
+        // msgctxt/msgid depois de um msgstr começam uma nova mensagem
+        let starts_message = trimmed.starts_with("msgctxt") || trimmed.starts_with("msgid ");
+        if starts_message && unit.as_ref().is_some_and(|u| !u.msgstr_lines.is_empty()) {
+            finish(&mut unit, &mut units);
+            field = Field::None;
+        }
+
+        if trimmed.starts_with('#') {
+            // Comentários sempre abrem uma nova mensagem
+            if unit.as_ref().is_some_and(|u| !u.msgstr_lines.is_empty()) {
+                finish(&mut unit, &mut units);
+                field = Field::None;
+            }
+            comments.push(line.to_string());
+            continue;
+        }
+
+        let current = unit.get_or_insert_with(PoUnit::new);
+        if !comments.is_empty() {
+            for comment in comments.drain(..) {
+                if let Some(flags) = comment.strip_prefix("#,") {
+                    current
+                        .flags
+                        .extend(flags.split(',').map(|f| f.trim().to_string()));
+                } else {
+                    current.comments.push(comment);
+                }
+            }
+        }
+
+        let error = || anyhow!("PO: invalid line {}: {}", number + 1, line);
+
+        if trimmed.starts_with('"') {
+            let text = unquote(trimmed).ok_or_else(error)?;
+            match field {
+                Field::Context => {
+                    current
+                        .msgctxt
+                        .get_or_insert_with(String::new)
+                        .push_str(&text);
+                    current.source_lines.push(line.to_string());
+                }
+                Field::Id => {
+                    current.msgid.push_str(&text);
+                    current.source_lines.push(line.to_string());
+                }
+                Field::Plural => {
+                    current
+                        .msgid_plural
+                        .get_or_insert_with(String::new)
+                        .push_str(&text);
+                    current.source_lines.push(line.to_string());
+                }
+                Field::Str(n) => {
+                    current.msgstr[n].push_str(&text);
+                    current.msgstr_lines[n].push(line.to_string());
+                }
+                Field::None => return Err(error()),
+            }
+            continue;
+        }
+
+        let (keyword, rest) = trimmed.split_once(char::is_whitespace).ok_or_else(error)?;
+        let text = unquote(rest.trim()).ok_or_else(error)?;
+        match keyword {
+            "msgctxt" => {
+                current.msgctxt = Some(text);
+                current.source_lines.push(line.to_string());
+                field = Field::Context;
+            }
+            "msgid" => {
+                current.msgid = text;
+                current.source_lines.push(line.to_string());
+                field = Field::Id;
+            }
+            "msgid_plural" => {
+                current.msgid_plural = Some(text);
+                current.source_lines.push(line.to_string());
+                field = Field::Plural;
+            }
+            _ if keyword == "msgstr" || keyword.starts_with("msgstr[") => {
+                let n = if keyword == "msgstr" {
+                    0
+                } else {
+                    keyword
+                        .trim_start_matches("msgstr[")
+                        .trim_end_matches(']')
+                        .parse()
+                        .map_err(|_| error())?
+                };
+                if current.msgstr.len() <= n {
+                    current.msgstr.resize(n + 1, String::new());
+                    current.msgstr_lines.resize(n + 1, Vec::new());
+                }
+                current.msgstr[n] = text;
+                current.msgstr_lines[n] = vec![line.to_string()];
+                field = Field::Str(n);
+            }
+            _ => return Err(error()),
+        }
+    }
+    finish(&mut unit, &mut units);
+    while comments.last().is_some_and(|c| c.is_empty()) {
+        comments.pop();
+    }
+
+    Ok(PoFile {
+        units,
+        trailing: comments,
+        slots: Vec::new(),
+    })
+}
+
+// Converte as mensagens em entradas da tabela. Mensagens com plural geram uma
+// entrada por forma (`chave [n]`); o cabeçalho não é editável.
+pub fn to_entries(file: &mut PoFile) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    file.slots.clear();
+    let nplurals = file.nplurals();
+
+    for (unit_index, unit) in file.units.iter().enumerate() {
+        if unit.is_header() {
+            continue;
+        }
+
+        let mut notes = Vec::new();
+        if let Some(ctx) = &unit.msgctxt {
+            notes.push(format!("msgctxt: {}", ctx));
+        }
+        notes.extend(
+            unit.comments
+                .iter()
+                .filter(|c| !c.is_empty() && !c.starts_with("#~") && !c.starts_with("#|"))
+                .cloned(),
+        );
+
+        let complete = unit.msgstr.iter().all(|s| !s.is_empty()) && !unit.msgstr.is_empty();
+        let status = if unit.is_fuzzy() {
+            EntryStatus::NeedsReview
+        } else if complete {
+            EntryStatus::Translated
+        } else {
+            EntryStatus::Untranslated
+        };
+
+        let forms = unit.plural_count(nplurals);
+        for form in 0..forms {
+            let (key, original) = match &unit.msgid_plural {
+                Some(plural) => (
+                    format!("{} [{}]", unit.key(), form),
+                    if form == 0 { &unit.msgid } else { plural },
+                ),
+                None => (unit.key(), &unit.msgid),
+            };
+            entries.push(Entry {
+                key,
+                original: Value::String(original.clone()),
+                // `msgstr` vazio é a forma do PO de dizer que não há tradução
+                translated: unit
+                    .msgstr
+                    .get(form)
+                    .filter(|text| !text.is_empty())
+                    .cloned()
+                    .map_or(Value::Null, Value::String),
+                status,
+                lock: Lock::Unlocked,
+                max_length: None,
+                dirty: false,
+                modified: None,
+                notes: notes.clone(),
+            });
+            file.slots.push((unit_index, form));
+        }
+    }
+
+    entries
+}
+
+// Copia traduções de um PO já existente (mesmo msgctxt/msgid) para o modelo.
+pub fn merge_existing(template: &mut PoFile, existing: &PoFile) {
+    for unit in template.units.iter_mut().filter(|u| !u.is_header()) {
+        if let Some(found) = existing
+            .units
+            .iter()
+            .find(|e| e.msgctxt == unit.msgctxt && e.msgid == unit.msgid)
+        {
+            unit.msgstr = found.msgstr.clone();
+            unit.msgstr_lines = found.msgstr_lines.clone();
+            unit.flags.retain(|f| f != "fuzzy");
+            if found.is_fuzzy() {
+                unit.flags.push("fuzzy".to_string());
+            }
+        }
+    }
+}
+
+pub fn save(file: &mut PoFile, entries: &[Entry], path: &Path) -> Result<()> {
+    // Formas além de `nplurals` (de um `.pot` com mais formas que o idioma) saem
+    let mut forms = vec![0; file.units.len()];
+    for &(unit_index, _) in &file.slots {
+        forms[unit_index] += 1;
+    }
+    for (unit, &count) in file.units.iter_mut().zip(&forms) {
+        if unit.msgid_plural.is_some() && count > 0 {
+            unit.msgstr.truncate(count);
+            unit.msgstr_lines.truncate(count);
+        }
+    }
+
+    for (entry, &(unit_index, form)) in entries.iter().zip(&file.slots) {
+        let unit = &mut file.units[unit_index];
+        if unit.msgstr.len() <= form {
+            unit.msgstr.resize(form + 1, String::new());
+            unit.msgstr_lines.resize(form + 1, Vec::new());
+        }
+        // Entradas bloqueadas repetem o texto de origem
+        let value = if entry.is_locked() {
+            text_of(&entry.original)
+        } else {
+            text_of(&entry.translated)
+        };
+        if unit.msgstr[form] != value {
+            unit.msgstr[form] = value;
+            unit.msgstr_lines[form].clear();
+        }
+    }
+
+    // Uma mensagem fica `fuzzy` se qualquer uma das suas formas estiver em
+    // revisão ou for rascunho da tradução automática
+    let mut fuzzy = vec![false; file.units.len()];
+    for (entry, &(unit_index, _)) in entries.iter().zip(&file.slots) {
+        if matches!(entry.status, EntryStatus::NeedsReview | EntryStatus::Draft) {
+            fuzzy[unit_index] = true;
+        }
+    }
+    for (unit_index, unit) in file.units.iter_mut().enumerate() {
+        if unit.is_header() {
+            continue;
+        }
+        unit.flags.retain(|f| f != "fuzzy");
+        if fuzzy[unit_index] {
+            unit.flags.insert(0, "fuzzy".to_string());
+        }
+    }
+
+    fs::write(path, render(file))?;
+    Ok(())
+}
+
+fn render(file: &PoFile) -> String {
+    let mut blocks = Vec::new();
+
+    for unit in &file.units {
+        let mut lines: Vec<String> = Vec::new();
+        // Mantém a posição original dos comentários: os de referência e
+        // extraídos vêm antes da linha de flags, como o xgettext gera.
+        let split = unit
+            .comments
+            .iter()
+            .position(|c| c.starts_with("#|"))
+            .unwrap_or(unit.comments.len());
+        lines.extend(unit.comments[..split].iter().cloned());
+        if !unit.flags.is_empty() {
+            lines.push(format!("#, {}", unit.flags.join(", ")));
+        }
+        lines.extend(unit.comments[split..].iter().cloned());
+        lines.extend(unit.source_lines.iter().cloned());
+
+        let plural = unit.msgid_plural.is_some();
+        for (n, text) in unit.msgstr.iter().enumerate() {
+            match unit.msgstr_lines.get(n) {
+                Some(raw) if !raw.is_empty() => lines.extend(raw.iter().cloned()),
+                _ => {
+                    let keyword = if plural {
+                        format!("msgstr[{}]", n)
+                    } else {
+                        "msgstr".to_string()
+                    };
+                    lines.extend(quote_field(&keyword, text));
+                }
+            }
+        }
+        blocks.push(lines.join("\n"));
+    }
+
+    if !file.trailing.is_empty() {
+        blocks.push(file.trailing.join("\n"));
+    }
+
+    let mut output = blocks.join("\n\n");
+    output.push('\n');
+    output
+}
+
+fn quote_field(keyword: &str, text: &str) -> Vec<String> {
+    let parts: Vec<&str> = text.split_inclusive('\n').collect();
+    if parts.len() <= 1 {
+        return vec![format!("{} \"{}\"", keyword, escape(text))];
+    }
+    let mut lines = vec![format!("{} \"\"", keyword)];
+    lines.extend(parts.iter().map(|p| format!("\"{}\"", escape(p))));
+    lines
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn unquote(text: &str) -> Option<String> {
+    let inner = text.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            'a' => out.push('\u{7}'),
+            'b' => out.push('\u{8}'),
+            'f' => out.push('\u{c}'),
+            'v' => out.push('\u{b}'),
+            other => out.push(other),
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn fixtures() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/po")
+    }
+
+    fn fixture(name: &str) -> PoFile {
+        parse(&fs::read_to_string(fixtures().join(name)).unwrap()).unwrap()
+    }
+
+    fn translate(entries: &mut [Entry], key: &str, text: &str, status: EntryStatus) {
+        let entry = entries
+            .iter_mut()
+            .find(|entry| entry.key == key)
+            .unwrap_or_else(|| panic!("missing key {}", key));
+        entry.translated = Value::String(text.to_string());
+        entry.status = status;
+    }
+
+    // Grava numa pasta temporária e compara com `expected/<nome>`
+    fn assert_saved(name: &str, file: &mut PoFile, entries: &[Entry]) {
+        let dir = std::env::temp_dir().join("transtui-po");
+        fs::create_dir_all(&dir).unwrap();
+        save(file, entries, &dir.join(name)).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join(name)).unwrap(),
+            fs::read_to_string(fixtures().join("expected").join(name)).unwrap()
+        );
+    }
+
+    #[test]
+    fn msgctxt_keeps_messages_with_the_same_msgid_apart() {
+        let mut file = fixture("context.po");
+        let mut entries = to_entries(&mut file);
+        let keys: Vec<&str> = entries.iter().map(|entry| entry.key.as_str()).collect();
+        assert_eq!(keys, ["[menu] Open", "[dialog] Open"]);
+        assert!(entries[0].status == EntryStatus::Untranslated);
+        assert!(entries[1].status == EntryStatus::Translated);
+
+        translate(
+            &mut entries,
+            "[menu] Open",
+            "Abrir…",
+            EntryStatus::Translated,
+        );
+        assert_saved("context.po", &mut file, &entries);
+    }
+
+    #[test]
+    fn plural_forms_come_from_the_header() {
+        let mut file = fixture("plurals.po");
+        assert_eq!(file.nplurals(), Some(3));
+        let mut entries = to_entries(&mut file);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(text_of(&entries[2].original), "%d files");
+
+        translate(
+            &mut entries,
+            "%d file [0]",
+            "%d plik",
+            EntryStatus::Translated,
+        );
+        translate(
+            &mut entries,
+            "%d file [1]",
+            "%d pliki",
+            EntryStatus::Translated,
+        );
+        translate(
+            &mut entries,
+            "%d file [2]",
+            "%d plików",
+            EntryStatus::Translated,
+        );
+        assert_saved("plurals.po", &mut file, &entries);
+    }
+
+    #[test]
+    fn fuzzy_flag_follows_the_review_status() {
+        let mut file = fixture("fuzzy.po");
+        let mut entries = to_entries(&mut file);
+        assert!(entries[0].status == EntryStatus::NeedsReview);
+        assert!(entries[1].status == EntryStatus::Translated);
+
+        translate(&mut entries, "Save", "Salvar", EntryStatus::Translated);
+        translate(&mut entries, "Delete", "Apagar", EntryStatus::NeedsReview);
+        assert_saved("fuzzy.po", &mut file, &entries);
+    }
+
+    #[test]
+    fn comments_and_obsolete_messages_are_preserved() {
+        let mut file = fixture("comments.po");
+        let mut entries = to_entries(&mut file);
+        assert_eq!(
+            entries[0].notes,
+            [
+                "# Keep it short",
+                "#. Button label",
+                "#: src/app.c:12 src/app.c:40"
+            ]
+        );
+
+        translate(
+            &mut entries,
+            "Exit %s",
+            "Sair de %s",
+            EntryStatus::Translated,
+        );
+        assert_saved("comments.po", &mut file, &entries);
+    }
+
+    #[test]
+    fn po_from_pot_gets_the_target_language_header() {
+        let dir = std::env::temp_dir().join("transtui-po-template");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("template.pot");
+        fs::copy(fixtures().join("template.pot"), &path).unwrap();
+
+        let config = Config {
+            target_language: "pl".to_string(),
+            ..Config::default()
+        };
+        let folder = dir.to_string_lossy().to_string();
+        let context = FormatContext {
+            translations_folder: &folder,
+            translation_suffix: "pl",
+            config: &config,
+        };
+        let (mut entries, mut document) = PoFormat.load(&path, &context).unwrap().unwrap();
+        assert_eq!(entries.len(), 3);
+
+        translate(
+            &mut entries,
+            "%d file [0]",
+            "%d plik",
+            EntryStatus::Translated,
+        );
+        PoFormat.save(&mut document, &entries, &context).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("template_pl.po")).unwrap(),
+            fs::read_to_string(fixtures().join("expected/template_pl.po")).unwrap()
+        );
+    }
+
+    #[test]
+    fn extra_template_forms_are_dropped_for_languages_with_fewer() {
+        let mut file = fixture("template.pot");
+        assert_eq!(file.nplurals(), None);
+        file.set_language("ja");
+        let entries = to_entries(&mut file);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "%d file [0]");
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::Result;
+
+use super::resource::{ResourceFile, ResourceKind, Slot};
+
+// Lê um `.properties` do Java. Linhas terminadas em `\` continuam na seguinte e
+// comentários (`#`, `!`) logo antes da chave viram notas.
+pub fn parse(content: String) -> Result<ResourceFile> {
+    let mut slots = Vec::new();
+    let mut notes: Vec<String> = Vec::new();
+
+    let mut position = 0;
+    while position < content.len() {
+        let line_end = content[position..]
+            .find('\n')
+            .map_or(content.len(), |i| position + i);
+        let line = content[position..line_end].trim_end_matches('\r');
+        let trimmed = line.trim_start();
+
+        if trimmed.is_empty() {
+            notes.clear();
+            position = line_end + 1;
+            continue;
+        }
+        if trimmed.starts_with(['#', '!']) {
+            notes.push(trimmed[1..].trim().to_string());
+            position = line_end + 1;
+            continue;
+        }
+
+        // Linha lógica: junta as continuações
+        let mut end = line_end;
+        while ends_with_continuation(content[position..end].trim_end_matches('\r'))
+            && end < content.len()
+        {
+            end = content[end + 1..]
+                .find('\n')
+                .map_or(content.len(), |i| end + 1 + i);
+        }
+        let logical = &content[position..end];
+        let start = position + (line.len() - trimmed.len());
+
+        let key_end = key_end(&content[start..end]) + start;
+        let mut value_start = key_end;
+        let rest = &content[key_end..end];
+        let skipped = rest.len() - rest.trim_start_matches([' ', '\t', '\x0c']).len();
+        value_start += skipped;
+        if content[value_start..end].starts_with(['=', ':']) {
+            value_start += 1;
+            let rest = &content[value_start..end];
+            value_start += rest.len() - rest.trim_start_matches([' ', '\t', '\x0c']).len();
+        }
+        let value_end = (position + logical.trim_end_matches('\r').len()).max(value_start);
+
+        slots.push(Slot {
+            key: decode(&content[start..key_end]),
+            range: value_start..value_end,
+            value: decode(&content[value_start..value_end]),
+            notes: std::mem::take(&mut notes),
+            markup: false,
+            insert: None,
+        });
+        position = end + 1;
+    }
+
+    Ok(ResourceFile::new(
+        ResourceKind::Properties,
+        content,
+        slots,
+        Vec::new(),
+    ))
+}
+
+// Um número ímpar de `\` no fim indica continuação
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|&c| c == '\\').count() % 2 == 1
+}
+
+// Fim da chave: primeiro `=`, `:` ou espaço que não esteja escapado
+fn key_end(text: &str) -> usize {
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '=' | ':' | ' ' | '\t' | '\x0c' => return i,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+fn decode(raw: &str) -> String {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => value.push('\t'),
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('f') => value.push('\x0c'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                if let Some(c) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    value.push(c);
+                }
+            }
+            // Continuação: a quebra e o recuo da linha seguinte não fazem parte do valor
+            Some('\r') | Some('\n') => {
+                while chars
+                    .peek()
+                    .is_some_and(|c| matches!(c, '\n' | ' ' | '\t' | '\x0c'))
+                {
+                    chars.next();
+                }
+            }
+            Some(other) => value.push(other),
+            None => {}
+        }
+    }
+    value
+}
+
+// Caracteres fora do ASCII vão como `\uXXXX`: o `Properties.load` do Java lê
+// o arquivo em ISO-8859-1
+pub fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for (i, c) in text.chars().enumerate() {
+        match c {
+            '\\' => encoded.push_str("\\\\"),
+            '\n' => encoded.push_str("\\n"),
+            '\t' => encoded.push_str("\\t"),
+            '\r' => encoded.push_str("\\r"),
+            '\x0c' => encoded.push_str("\\f"),
+            ' ' if i == 0 => encoded.push_str("\\ "),
+            other => encoded.push(other),
+        }
+    }
+    escape_non_ascii(&encoded)
+}
+
+// Escapa o que sobrou fora do ASCII no arquivo gravado (valores não traduzidos
+// e comentários), já que a origem pode ter sido lida em ISO-8859-1 ou UTF-8
+pub fn escape_non_ascii(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii() {
+            escaped.push(c);
+            continue;
+        }
+        let mut units = [0u16; 2];
+        for unit in c.encode_utf16(&mut units) {
+            escaped.push_str(&format!("\\u{:04x}", unit));
+        }
+    }
+    escaped
+}
@@ -0,0 +1,282 @@
+use anyhow::{Result, anyhow};
+use std::any::Any;
+use std::path::{Path, PathBuf};
+
+use super::{json, po, resource, yaml};
+use crate::app::Entry;
+use crate::config::Config;
+use crate::localization::Locale;
+
+// Pastas e idioma usados para achar e gravar as traduções de um arquivo
+pub struct FormatContext<'a> {
+    pub translations_folder: &'a str,
+    pub translation_suffix: &'a str,
+    pub config: &'a Config,
+}
+
+impl<'a> FormatContext<'a> {
+    pub fn new(locale: &'a Locale, config: &'a Config) -> Self {
+        Self {
+            translations_folder: locale.get("translations_folder"),
+            translation_suffix: locale.get("translation_suffix"),
+            config,
+        }
+    }
+}
+
+// Arquivo de origem aberto, guardado no tipo do formato que o leu. Só esse
+// formato sabe gravá-lo.
+pub struct Document(Box<dyn Any>);
+
+impl Document {
+    pub fn new<T: Any>(document: T) -> Self {
+        Self(Box::new(document))
+    }
+
+    // Falha quando o documento foi aberto por outro formato, em vez de gravar nada
+    pub fn get_mut<T: Any>(&mut self, format: &str) -> Result<&mut T> {
+        self.0
+            .downcast_mut()
+            .ok_or_else(|| anyhow!("document was not loaded by the {} format", format))
+    }
+}
+
+// Formato de arquivo de origem. Cada formato fica no próprio módulo, com o seu
+// tipo de documento; para suportar um tipo novo basta implementar este trait e
+// incluí-lo em `FORMATS`.
+pub trait Format: Sync {
+    // Nome exibido na seleção de arquivos
+    fn name(&self) -> &'static str;
+
+    fn extensions(&self) -> &'static [&'static str];
+
+    fn detect(&self, path: &Path) -> bool {
+        let extension = path.extension().unwrap_or_default().to_string_lossy();
+        self.extensions().contains(&extension.as_ref())
+    }
+
+    // Onde a tradução do arquivo é gravada; o próprio arquivo quando ele é
+    // editado no lugar
+    fn output_path(&self, path: &Path, context: &FormatContext) -> PathBuf;
+
+    // Lê o arquivo com as traduções já existentes. `None` quando o conteúdo
+    // não é do formato, apesar da extensão.
+    fn load(&self, path: &Path, context: &FormatContext) -> Result<Option<(Vec<Entry>, Document)>>;
+
+    // Grava as traduções. O arquivo de estados (`.toml`) fica a cargo de quem chama.
+    fn save(
+        &self,
+        document: &mut Document,
+        entries: &[Entry],
+        context: &FormatContext,
+    ) -> Result<()>;
+}
+
+// Ordem de detecção: o primeiro formato que reconhece o arquivo é usado
+pub static FORMATS: [&dyn Format; 4] = [
+    &po::PoFormat,
+    &yaml::YamlFormat,
+    &resource::ResourceFormat,
+    &json::JsonFormat,
+];
+
+pub fn find(path: &Path) -> Option<&'static dyn Format> {
+    FORMATS.iter().copied().find(|format| format.detect(path))
+}
+
+// Lista dos formatos suportados, para o título da seleção de arquivos
+pub fn names() -> String {
+    FORMATS
+        .iter()
+        .map(|format| format.name())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::EntryStatus;
+    use crate::file_operations;
+    use crate::formats::text_of;
+    use serde_json::Value;
+    use std::fs;
+
+    // Copia `tests/fixtures/<caso>` para uma pasta temporária, traduz as chaves
+    // indicadas e compara a saída gravada com `expected/`. Depois reabre o
+    // arquivo e confere se as traduções voltam.
+    fn round_trip(case: &str, source: &str, output: &str, translations: &[(&str, &str)]) {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(case);
+        let dir = std::env::temp_dir().join(format!("transtui-fixture-{}", case));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let dir = fs::canonicalize(dir).unwrap();
+        let path = dir.join(source);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::copy(fixtures.join(source), &path).unwrap();
+
+        let config = Config::default();
+        let folder = dir.join("out").to_string_lossy().to_string();
+        let context = FormatContext {
+            translations_folder: &folder,
+            translation_suffix: "pt",
+            config: &config,
+        };
+
+        let format = find(&path).expect("format not detected");
+        assert_eq!(format.output_path(&path, &context), dir.join(output));
+        let (mut entries, mut document) = format.load(&path, &context).unwrap().unwrap();
+        for (key, text) in translations {
+            let entry = entries
+                .iter_mut()
+                .find(|entry| entry.key == *key)
+                .unwrap_or_else(|| panic!("missing key {}", key));
+            entry.translated = Value::String(text.to_string());
+            entry.status = EntryStatus::Translated;
+        }
+        format.save(&mut document, &entries, &context).unwrap();
+        file_operations::save_translated_keys(&path.with_extension("toml"), &entries).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join(output)).unwrap(),
+            fs::read_to_string(fixtures.join("expected").join(output)).unwrap()
+        );
+
+        let (reloaded, _) = format.load(&path, &context).unwrap().unwrap();
+        for (key, text) in translations {
+            let entry = reloaded.iter().find(|entry| entry.key == *key).unwrap();
+            assert_eq!(text_of(&entry.translated), *text);
+            assert!(entry.status == EntryStatus::Translated);
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        round_trip(
+            "json",
+            "messages.json",
+            "out/messages_pt.json",
+            &[("title", "Configurações"), ("save", "Salvar")],
+        );
+    }
+
+    #[test]
+    fn arb_round_trip() {
+        round_trip(
+            "arb",
+            "app_en.arb",
+            "out/app_en_pt.arb",
+            &[("greeting", "Olá {name}")],
+        );
+    }
+
+    #[test]
+    fn chrome_round_trip() {
+        round_trip(
+            "chrome",
+            "_locales/en/messages.json",
+            "out/messages_pt.json",
+            &[("appName", "Guarda Abas")],
+        );
+    }
+
+    #[test]
+    fn po_round_trip() {
+        round_trip("po", "pt.po", "pt.po", &[("Open", "Abrir")]);
+    }
+
+    #[test]
+    fn pot_round_trip() {
+        round_trip(
+            "pot",
+            "messages.pot",
+            "out/messages_pt.po",
+            &[("Open", "Abrir"), ("Close", "Fechar")],
+        );
+    }
+
+    #[test]
+    fn yaml_round_trip() {
+        round_trip(
+            "yaml",
+            "messages.yml",
+            "out/messages_pt.yml",
+            &[
+                ("home.title", "Início"),
+                ("home.subtitle", "Bem-vindo de volta"),
+            ],
+        );
+    }
+
+    #[test]
+    fn android_round_trip() {
+        round_trip(
+            "android",
+            "values/strings.xml",
+            "values-pt/strings.xml",
+            &[("title", "Configurações")],
+        );
+    }
+
+    #[test]
+    fn strings_round_trip() {
+        round_trip(
+            "strings",
+            "en.lproj/Localizable.strings",
+            "pt.lproj/Localizable.strings",
+            &[("title", "Configurações"), ("save", "Salvar")],
+        );
+    }
+
+    #[test]
+    fn stringsdict_round_trip() {
+        round_trip(
+            "stringsdict",
+            "en.lproj/Localizable.stringsdict",
+            "pt.lproj/Localizable.stringsdict",
+            &[
+                ("files [count.one]", "%d arquivo"),
+                ("files [count.other]", "%d arquivos"),
+            ],
+        );
+    }
+
+    #[test]
+    fn properties_round_trip() {
+        round_trip(
+            "properties",
+            "messages.properties",
+            "messages_pt.properties",
+            &[("title", "Configurações")],
+        );
+    }
+
+    #[test]
+    fn fluent_round_trip() {
+        round_trip(
+            "fluent",
+            "en/main.ftl",
+            "pt/main.ftl",
+            &[
+                ("title", "Configurações"),
+                ("save.tooltip", "Salva o documento"),
+            ],
+        );
+    }
+
+    #[test]
+    fn saving_a_document_from_another_format_fails() {
+        let config = Config::default();
+        let context = FormatContext {
+            translations_folder: "out",
+            translation_suffix: "pt",
+            config: &config,
+        };
+        for format in FORMATS {
+            let mut document = Document::new(());
+            assert!(format.save(&mut document, &[], &context).is_err());
+        }
+    }
+}
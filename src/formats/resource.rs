@@ -0,0 +1,532 @@
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use super::registry::{Document, Format, FormatContext};
+use super::{LOCALE_CODE, android, apple, fluent, output_value, properties, read, text_of};
+use crate::app::{Entry, EntryStatus, Lock};
+use crate::config::Config;
+use crate::file_operations;
+
+// Recurso aberto; a tradução fica na pasta da plataforma para o idioma de destino
+pub struct ResourceDocument {
+    file: ResourceFile,
+    output: PathBuf,
+}
+
+// Recursos do Android (`strings.xml`), do iOS (`.strings`, `.stringsdict`), do
+// Java (`.properties`) e do Fluent (`.ftl`)
+pub struct ResourceFormat;
+
+impl Format for ResourceFormat {
+    fn name(&self) -> &'static str {
+        "XML, STRINGS, PROPERTIES, FTL"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["xml", "strings", "stringsdict", "properties", "ftl"]
+    }
+
+    // Só arquivos `<resources>` do Android entre os `.xml`
+    fn detect(&self, path: &Path) -> bool {
+        match ResourceKind::from_path(path) {
+            Some(ResourceKind::Android) => {
+                fs::read_to_string(path).is_ok_and(|content| content.contains("<resources"))
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    fn output_path(&self, path: &Path, context: &FormatContext) -> PathBuf {
+        match ResourceKind::from_path(path) {
+            Some(kind) => output_path(path, kind, &context.config.target_language),
+            None => path.to_path_buf(),
+        }
+    }
+
+    fn load(&self, path: &Path, context: &FormatContext) -> Result<Option<(Vec<Entry>, Document)>> {
+        let kind =
+            ResourceKind::from_path(path).ok_or_else(|| anyhow!("unsupported resource file"))?;
+        let language = &context.config.target_language;
+        let file = parse(kind, read(path)?, language)?;
+        let output = output_path(path, kind, language);
+        let existing = if output.exists() {
+            parse(kind, read(&output)?, language)?.values()
+        } else {
+            HashMap::new()
+        };
+
+        let mut entries = to_entries(&file, &existing);
+        file_operations::apply_sidecar(&mut entries, path, context.config)?;
+        Ok(Some((
+            entries,
+            Document::new(ResourceDocument { file, output }),
+        )))
+    }
+
+    fn save(
+        &self,
+        document: &mut Document,
+        entries: &[Entry],
+        context: &FormatContext,
+    ) -> Result<()> {
+        let ResourceDocument { file, output } = document.get_mut(self.name())?;
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        save(file, entries, context.config, output)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ResourceKind {
+    Android,
+    Strings,
+    StringsDict,
+    Properties,
+    Fluent,
+}
+
+impl ResourceKind {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "xml" => Some(Self::Android),
+            "strings" => Some(Self::Strings),
+            "stringsdict" => Some(Self::StringsDict),
+            "properties" => Some(Self::Properties),
+            "ftl" => Some(Self::Fluent),
+            _ => None,
+        }
+    }
+}
+
+// Trecho editável do arquivo: o intervalo do valor no texto de origem e o valor lido
+pub struct Slot {
+    pub key: String,
+    pub range: Range<usize>,
+    pub value: String,
+    pub notes: Vec<String>,
+    // Strings do Android com marcação (`<b>`, `<xliff:g>`) mantêm as tags sem escape
+    pub markup: bool,
+    // Forma de plural que a origem não tem: o elemento é criado em `range`
+    // (vazio), com o valor entre os dois textos
+    pub insert: Option<(String, String)>,
+}
+
+// Uma forma de `<plurals>` ou de uma variável do `.stringsdict`: o trecho do
+// elemento inteiro no arquivo e o seu valor
+pub struct PluralItem {
+    pub category: String,
+    pub range: Range<usize>,
+    pub slot: Slot,
+}
+
+// Ajusta as formas do plural às categorias do idioma de destino. As que o
+// idioma não usa saem do arquivo; as que faltam são criadas com o texto de
+// `other`, antes da forma seguinte na ordem do CLDR. `element` devolve o texto
+// antes e depois do valor para uma categoria, com o recuo da linha.
+pub fn plural_slots(
+    content: &str,
+    items: Vec<PluralItem>,
+    categories: &[&str],
+    key: impl Fn(&str) -> String,
+    element: impl Fn(&str, &str) -> (String, String),
+    slots: &mut Vec<Slot>,
+    removed: &mut Vec<Range<usize>>,
+) {
+    let Some(reference) = items
+        .iter()
+        .find(|item| item.category == "other")
+        .or(items.last())
+    else {
+        return;
+    };
+    let (value, notes, markup) = (
+        reference.slot.value.clone(),
+        reference.slot.notes.clone(),
+        reference.slot.markup,
+    );
+    let first_start = items[0].range.start;
+
+    let (kept, dropped): (Vec<PluralItem>, Vec<PluralItem>) = items
+        .into_iter()
+        .partition(|item| categories.contains(&item.category.as_str()));
+    removed.extend(
+        dropped
+            .iter()
+            .map(|item| whole_lines(content, item.range.clone())),
+    );
+    let last_end = kept.iter().map(|item| item.range.end).max();
+    let starts: Vec<(String, usize)> = kept
+        .iter()
+        .map(|item| (item.category.clone(), item.range.start))
+        .collect();
+    let mut kept: Vec<Option<PluralItem>> = kept.into_iter().map(Some).collect();
+
+    for (index, category) in categories.iter().enumerate() {
+        let existing = kept
+            .iter_mut()
+            .find(|item| item.as_ref().is_some_and(|item| item.category == *category));
+        if let Some(item) = existing.and_then(Option::take) {
+            slots.push(item.slot);
+            continue;
+        }
+
+        // Antes da próxima forma que existe; sem nenhuma, depois da última.
+        // Se a origem não tem nenhuma forma do idioma, no lugar da primeira.
+        let next = categories[index + 1..].iter().find_map(|next| {
+            starts
+                .iter()
+                .find(|(category, _)| category == next)
+                .map(|&(_, start)| start)
+        });
+        let (at, before, after) = match (next, last_end) {
+            (None, Some(end)) => {
+                let (open, close) = element(category, line_indent(content, end));
+                (end, format!("\n{}", open), close)
+            }
+            (next, _) => {
+                let start = next.unwrap_or(first_start);
+                let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+                let indent = &content[line_start..start];
+                if indent.trim().is_empty() {
+                    let (open, close) = element(category, indent);
+                    (line_start, open, format!("{}\n", close))
+                } else {
+                    let (open, close) = element(category, "");
+                    (start, open, close)
+                }
+            }
+        };
+        slots.push(Slot {
+            key: key(category),
+            range: at..at,
+            value: value.clone(),
+            notes: notes.clone(),
+            markup,
+            insert: Some((before, after)),
+        });
+    }
+}
+
+// Recuo da linha em que está `position`
+fn line_indent(content: &str, position: usize) -> &str {
+    let line_start = content[..position].rfind('\n').map_or(0, |i| i + 1);
+    let line = &content[line_start..position];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+// Estende o intervalo de um elemento removido para a linha inteira, quando ele
+// está sozinho nela, para não deixar linhas em branco no arquivo gravado
+pub fn whole_lines(content: &str, range: Range<usize>) -> Range<usize> {
+    let line_start = content[..range.start].rfind('\n').map_or(0, |i| i + 1);
+    let start = if content[line_start..range.start].trim().is_empty() {
+        line_start
+    } else {
+        range.start
+    };
+    let rest = &content[range.end..];
+    let end = match rest.find('\n') {
+        Some(i) if rest[..i].trim().is_empty() => range.end + i + 1,
+        _ => range.end,
+    };
+    start..end
+}
+
+// Representação comum dos formatos de chave e valor (Android, iOS, Java,
+// Fluent). O texto original é regravado trocando apenas os valores traduzidos,
+// o que preserva comentários e formatação; cada formato só precisa ler os
+// trechos editáveis e saber escrever um valor.
+pub struct ResourceFile {
+    pub kind: ResourceKind,
+    source: String,
+    slots: Vec<Slot>,
+    // Elementos que não devem ir para a tradução (`translatable="false"`)
+    removed: Vec<Range<usize>>,
+}
+
+impl ResourceFile {
+    pub fn new(
+        kind: ResourceKind,
+        source: String,
+        slots: Vec<Slot>,
+        removed: Vec<Range<usize>>,
+    ) -> Self {
+        Self {
+            kind,
+            source,
+            slots,
+            removed,
+        }
+    }
+
+    pub fn values(&self) -> HashMap<String, String> {
+        self.slots
+            .iter()
+            .map(|slot| (slot.key.clone(), slot.value.clone()))
+            .collect()
+    }
+}
+
+// `language` é o idioma de destino, que define as formas de plural
+pub fn parse(kind: ResourceKind, content: String, language: &str) -> Result<ResourceFile> {
+    match kind {
+        ResourceKind::Android => android::parse(content, language),
+        ResourceKind::Strings => apple::parse_strings(content),
+        ResourceKind::StringsDict => apple::parse_stringsdict(content, language),
+        ResourceKind::Properties => properties::parse(content),
+        ResourceKind::Fluent => fluent::parse(content),
+    }
+}
+
+// Local da tradução conforme a convenção de cada formato: `values-pt/` no
+// Android, `pt.lproj/` no iOS e `pt/` no Fluent, ao lado da pasta do idioma de
+// origem quando houver; no Java, `<nome>_pt.properties` na mesma pasta.
+pub fn output_path(path: &Path, kind: ResourceKind, target_language: &str) -> PathBuf {
+    if kind == ResourceKind::Properties {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        // `mensagens_en` vira `mensagens_pt`
+        let base = match stem.rsplit_once('_') {
+            Some((base, locale)) if LOCALE_CODE.is_match(locale) => base,
+            _ => &stem,
+        };
+        let filename = format!("{}_{}.properties", base, target_language.replace('-', "_"));
+        return path.with_file_name(filename);
+    }
+
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let dir = fs::canonicalize(parent).unwrap_or_else(|_| parent.to_path_buf());
+    let dir_name = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let (is_locale_dir, folder) = match kind {
+        ResourceKind::Android => (
+            dir_name == "values" || dir_name.starts_with("values-"),
+            format!("values-{}", android_qualifier(target_language)),
+        ),
+        ResourceKind::Strings | ResourceKind::StringsDict => (
+            dir_name.ends_with(".lproj"),
+            format!("{}.lproj", target_language),
+        ),
+        ResourceKind::Fluent | ResourceKind::Properties => {
+            (LOCALE_CODE.is_match(&dir_name), target_language.to_string())
+        }
+    };
+
+    let base = match dir.parent() {
+        Some(base) if is_locale_dir => base.to_path_buf(),
+        _ => dir.clone(),
+    };
+    base.join(folder).join(path.file_name().unwrap_or_default())
+}
+
+// `pt-BR` vira `pt-rBR`, como o Android espera nos qualificadores de pasta
+fn android_qualifier(language: &str) -> String {
+    match language.split_once(['-', '_']) {
+        Some((lang, region)) => format!("{}-r{}", lang, region.to_uppercase()),
+        None => language.to_string(),
+    }
+}
+
+pub fn to_entries(file: &ResourceFile, existing: &HashMap<String, String>) -> Vec<Entry> {
+    file.slots
+        .iter()
+        .map(|slot| Entry {
+            key: slot.key.clone(),
+            original: Value::String(slot.value.clone()),
+            translated: existing
+                .get(&slot.key)
+                .cloned()
+                .map_or(Value::Null, Value::String),
+            status: EntryStatus::Untranslated,
+            lock: Lock::Unlocked,
+            max_length: None,
+            dirty: false,
+            modified: None,
+            notes: slot.notes.clone(),
+        })
+        .collect()
+}
+
+// Sem como remover só a chave, `omit` grava o texto de origem
+pub fn save(file: &ResourceFile, entries: &[Entry], config: &Config, path: &Path) -> Result<()> {
+    let mut cuts: Vec<(Range<usize>, Option<String>)> = file
+        .removed
+        .iter()
+        .map(|range| (range.clone(), None))
+        .collect();
+    for (slot, entry) in file.slots.iter().zip(entries) {
+        let value = output_value(entry, config)
+            .map(|value| text_of(&value))
+            .unwrap_or_else(|| slot.value.clone());
+        if value != slot.value || slot.insert.is_some() {
+            let encoded = match file.kind {
+                ResourceKind::Android => android::encode(&value, slot.markup),
+                ResourceKind::Strings => apple::encode_strings(&value),
+                ResourceKind::StringsDict => apple::encode_plist(&value),
+                ResourceKind::Properties => properties::encode(&value),
+                ResourceKind::Fluent => fluent::encode(&value),
+            };
+            let text = match &slot.insert {
+                Some((before, after)) => format!("{}{}{}", before, encoded, after),
+                None => encoded,
+            };
+            cuts.push((slot.range.clone(), Some(text)));
+        }
+    }
+    // Um elemento criado vem antes de um removido que começa no mesmo ponto
+    cuts.sort_by_key(|(range, _)| (range.start, range.end));
+
+    let mut content = String::with_capacity(file.source.len());
+    let mut position = 0;
+    for (range, replacement) in cuts {
+        content.push_str(&file.source[position..range.start]);
+        if let Some(text) = replacement {
+            content.push_str(&text);
+        }
+        position = range.end;
+    }
+    content.push_str(&file.source[position..]);
+    if file.kind == ResourceKind::Properties {
+        content = properties::escape_non_ascii(&content);
+    }
+
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latin1_properties_are_written_as_ascii() {
+        let dir = std::env::temp_dir().join("transtui-properties-latin1");
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("messages.properties");
+        // `# Opções` e `title=Título` em ISO-8859-1
+        fs::write(&source, b"# Op\xe7\xf5es\ntitle=T\xedtulo\nsave=Save\n").unwrap();
+
+        let file = parse(ResourceKind::Properties, read(&source).unwrap(), "pt-BR").unwrap();
+        let mut entries = to_entries(&file, &HashMap::new());
+        entries[1].translated = Value::String("Salvar ação".to_string());
+        let output = output_path(&source, ResourceKind::Properties, "pt-BR");
+        save(&file, &entries, &Config::default(), &output).unwrap();
+
+        let written = fs::read(&output).unwrap();
+        assert!(written.is_ascii());
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            "# Op\\u00e7\\u00f5es\ntitle=T\\u00edtulo\nsave=Salvar a\\u00e7\\u00e3o\n"
+        );
+        assert_eq!(output, dir.join("messages_pt_BR.properties"));
+    }
+
+    // Lê `tests/fixtures/plurals/<nome>` para `language`, traduz as chaves
+    // indicadas e retorna as chaves e o texto gravado
+    fn expand(name: &str, language: &str, translations: &[(&str, &str)]) -> (Vec<String>, String) {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/plurals");
+        let path = fixtures.join(name);
+        let kind = ResourceKind::from_path(&path).unwrap();
+        let file = parse(kind, read(&path).unwrap(), language).unwrap();
+        let mut entries = to_entries(&file, &HashMap::new());
+        for (key, text) in translations {
+            let entry = entries
+                .iter_mut()
+                .find(|entry| entry.key == *key)
+                .unwrap_or_else(|| panic!("missing key {}", key));
+            entry.translated = Value::String(text.to_string());
+            entry.status = EntryStatus::Translated;
+        }
+
+        let dir = std::env::temp_dir().join("transtui-plurals");
+        fs::create_dir_all(&dir).unwrap();
+        let output = dir.join(format!("{}-{}", language, name));
+        save(&file, &entries, &Config::default(), &output).unwrap();
+        let keys = entries.into_iter().map(|entry| entry.key).collect();
+        (keys, fs::read_to_string(output).unwrap())
+    }
+
+    fn expected(name: &str) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/plurals/expected");
+        fs::read_to_string(path.join(name)).unwrap()
+    }
+
+    #[test]
+    fn android_plurals_get_the_polish_categories() {
+        let (keys, output) = expand(
+            "strings.xml",
+            "pl",
+            &[
+                ("files [one]", "%d plik"),
+                ("files [few]", "%d pliki"),
+                ("files [many]", "%d plików"),
+                ("files [other]", "%d pliku"),
+            ],
+        );
+        assert_eq!(
+            keys,
+            [
+                "files [one]",
+                "files [few]",
+                "files [many]",
+                "files [other]",
+                "title"
+            ]
+        );
+        assert_eq!(output, expected("pl-strings.xml"));
+    }
+
+    #[test]
+    fn android_plurals_drop_categories_the_language_lacks() {
+        let (keys, output) = expand("strings.xml", "ja", &[("files [other]", "%d 個のファイル")]);
+        assert_eq!(keys, ["files [other]", "title"]);
+        assert_eq!(output, expected("ja-strings.xml"));
+    }
+
+    #[test]
+    fn portuguese_plurals_include_many() {
+        let (keys, _) = expand("strings.xml", "pt-BR", &[]);
+        assert_eq!(
+            keys,
+            ["files [one]", "files [many]", "files [other]", "title"]
+        );
+    }
+
+    #[test]
+    fn stringsdict_gets_the_russian_categories_and_keeps_zero() {
+        let (keys, output) = expand(
+            "Localizable.stringsdict",
+            "ru",
+            &[
+                ("files [count.zero]", "Нет файлов"),
+                ("files [count.one]", "%d файл"),
+                ("files [count.few]", "%d файла"),
+                ("files [count.many]", "%d файлов"),
+            ],
+        );
+        assert_eq!(
+            keys,
+            [
+                "files",
+                "files [count.zero]",
+                "files [count.one]",
+                "files [count.few]",
+                "files [count.many]",
+                "files [count.other]"
+            ]
+        );
+        assert_eq!(output, expected("ru-Localizable.stringsdict"));
+    }
+}
@@ -0,0 +1,112 @@
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+
+use super::text_of;
+use crate::app::{Entry, EntryStatus};
+
+const BOM: &str = "\u{feff}";
+const HEADER: [&str; 5] = ["key", "original", "translated", "status", "notes"];
+
+// Linha lida de uma planilha devolvida. Células vazias ou colunas ausentes
+// ficam como `None` e não alteram a entrada.
+pub struct SheetRow {
+    pub key: String,
+    pub translated: Option<String>,
+    pub status: Option<EntryStatus>,
+}
+
+// `.tsv` separa por tabulação; os demais por vírgula
+pub fn delimiter(path: &Path) -> u8 {
+    if path.extension().unwrap_or_default() == "tsv" {
+        b'\t'
+    } else {
+        b','
+    }
+}
+
+// Gera a planilha com chave, origem, tradução, estado e notas. O BOM faz o
+// Excel abrir o arquivo como UTF-8.
+pub fn export(entries: &[Entry], delimiter: u8, bom: bool) -> Result<Vec<u8>> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(Vec::new());
+    writer.write_record(HEADER)?;
+    for entry in entries {
+        // Entradas ainda não traduzidas saem com a tradução vazia
+        writer.write_record([
+            entry.key.as_str(),
+            &text_of(&entry.original),
+            &text_of(&entry.translated),
+            status_name(entry.status),
+            &entry.notes.join("\n"),
+        ])?;
+    }
+
+    let mut content = if bom {
+        BOM.as_bytes().to_vec()
+    } else {
+        Vec::new()
+    };
+    content.extend(writer.into_inner()?);
+    Ok(content)
+}
+
+pub fn status_name(status: EntryStatus) -> &'static str {
+    match status {
+        EntryStatus::Untranslated => "untranslated",
+        EntryStatus::NeedsReview => "review",
+        EntryStatus::Translated => "translated",
+        EntryStatus::Draft => "draft",
+    }
+}
+
+pub fn parse_status(value: &str) -> Option<EntryStatus> {
+    match value.trim().to_lowercase().as_str() {
+        "untranslated" | "new" => Some(EntryStatus::Untranslated),
+        "review" | "needs review" | "needs-review" => Some(EntryStatus::NeedsReview),
+        "translated" | "done" => Some(EntryStatus::Translated),
+        "draft" | "machine" | "mt" => Some(EntryStatus::Draft),
+        _ => None,
+    }
+}
+
+// Lê a planilha pelos nomes das colunas do cabeçalho, em qualquer ordem. Só a
+// coluna `key` é obrigatória.
+pub fn import(content: &str, delimiter: u8) -> Result<Vec<SheetRow>> {
+    let content = content.strip_prefix(BOM).unwrap_or(content);
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim().eq_ignore_ascii_case(name))
+    };
+    let Some(key_column) = column("key") else {
+        bail!("missing \"key\" column");
+    };
+    let translated_column = column("translated");
+    let status_column = column("status");
+
+    let mut rows = Vec::new();
+    for (line, record) in reader.records().enumerate() {
+        let record = record.with_context(|| format!("row {}", line + 2))?;
+        let cell = |column: Option<usize>| {
+            column
+                .and_then(|column| record.get(column))
+                .filter(|value| !value.is_empty())
+        };
+        let Some(key) = cell(Some(key_column)) else {
+            continue;
+        };
+        rows.push(SheetRow {
+            key: key.to_string(),
+            translated: cell(translated_column).map(str::to_string),
+            status: cell(status_column).and_then(parse_status),
+        });
+    }
+    Ok(rows)
+}
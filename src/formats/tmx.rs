@@ -0,0 +1,258 @@
+use anyhow::{Result, bail};
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use quick_xml::{escape::escape, events::Event, reader::Reader};
+use std::collections::BTreeMap;
+
+use super::attribute;
+use crate::memory::Segment;
+
+// Formato das datas do TMX (UTC)
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+// Propriedades com a origem do segmento na memória
+const KEY_PROP: &str = "x-key";
+const FILE_PROP: &str = "x-file";
+// Prefixo dos atributos que vieram de `<prop>`; os demais são atributos do `<tu>`
+const PROP_PREFIX: &str = "prop:";
+
+// Gera um TMX 1.4 com um `<tu>` por segmento, com datas, chave, arquivo e os
+// atributos recebidos em importações anteriores
+pub fn export(segments: &[&Segment]) -> String {
+    let source_language = match segments.first() {
+        Some(first)
+            if segments
+                .iter()
+                .all(|s| s.source_language == first.source_language) =>
+        {
+            first.source_language.as_str()
+        }
+        _ => "*all*",
+    };
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<tmx version=\"1.4\">\n");
+    out.push_str(&format!(
+        "  <header creationtool=\"transtui\" creationtoolversion=\"{}\" segtype=\"sentence\" o-tmf=\"transtui\" adminlang=\"en\" srclang=\"{}\" datatype=\"plaintext\"/>\n",
+        env!("CARGO_PKG_VERSION"),
+        escape(source_language)
+    ));
+    out.push_str("  <body>\n");
+
+    for segment in segments {
+        out.push_str("    <tu");
+        if let Some(date) = tmx_date(&segment.created) {
+            out.push_str(&format!(" creationdate=\"{}\"", date));
+        }
+        for (name, value) in &segment.attributes {
+            if !name.starts_with(PROP_PREFIX) {
+                out.push_str(&format!(" {}=\"{}\"", name, escape(value)));
+            }
+        }
+        out.push_str(">\n");
+
+        let mut props = vec![(KEY_PROP, &segment.key), (FILE_PROP, &segment.file)];
+        props.extend(
+            segment
+                .attributes
+                .iter()
+                .filter_map(|(name, value)| Some((name.strip_prefix(PROP_PREFIX)?, value))),
+        );
+        for (name, value) in props {
+            if !value.is_empty() {
+                out.push_str(&format!(
+                    "      <prop type=\"{}\">{}</prop>\n",
+                    escape(name),
+                    escape(value)
+                ));
+            }
+        }
+        for (language, text) in [
+            (&segment.source_language, &segment.source),
+            (&segment.target_language, &segment.target),
+        ] {
+            out.push_str(&format!(
+                "      <tuv xml:lang=\"{}\"><seg>{}</seg></tuv>\n",
+                escape(language),
+                escape(text)
+            ));
+        }
+        out.push_str("    </tu>\n");
+    }
+
+    out.push_str("  </body>\n</tmx>\n");
+    out
+}
+
+fn tmx_date(created: &str) -> Option<String> {
+    let date = DateTime::parse_from_rfc3339(created).ok()?;
+    Some(date.with_timezone(&Utc).format(DATE_FORMAT).to_string())
+}
+
+fn rfc3339_date(date: &str) -> Option<String> {
+    let date = NaiveDateTime::parse_from_str(date, DATE_FORMAT).ok()?;
+    Some(date.and_utc().with_timezone(&Local).to_rfc3339())
+}
+
+// Unidade em leitura: atributos, propriedades e as variantes por idioma
+#[derive(Default)]
+struct Unit {
+    attributes: BTreeMap<String, String>,
+    variants: Vec<(String, String)>,
+}
+
+// Lê os segmentos de um TMX. A variante no idioma de origem do cabeçalho é a
+// origem e cada uma das outras vira um segmento; com `srclang="*all*"` a
+// primeira variante é a origem.
+pub fn import(content: &str) -> Result<Vec<Segment>> {
+    let mut reader = Reader::from_str(content);
+    let mut segments = Vec::new();
+    let mut version = None;
+    let mut source_language = String::from("*all*");
+    let mut unit: Option<Unit> = None;
+    let mut language: Option<String> = None;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) | Event::Empty(element)
+                if element.local_name().as_ref() == b"tmx" =>
+            {
+                version = attribute(&element, "version")?;
+            }
+            Event::Start(element) | Event::Empty(element)
+                if element.local_name().as_ref() == b"header" =>
+            {
+                if let Some(language) = attribute(&element, "srclang")? {
+                    source_language = language;
+                }
+            }
+            Event::Start(element) => match element.local_name().as_ref() {
+                b"tu" => {
+                    let mut attributes = BTreeMap::new();
+                    for found in element.attributes() {
+                        let found = found?;
+                        let name = String::from_utf8_lossy(found.key.as_ref()).to_string();
+                        attributes.insert(name, found.unescape_value()?.to_string());
+                    }
+                    unit = Some(Unit {
+                        attributes,
+                        variants: Vec::new(),
+                    });
+                }
+                b"prop" => {
+                    let name = attribute(&element, "type")?.unwrap_or_default();
+                    let value = reader.read_text(element.name())?.to_string();
+                    let value = quick_xml::escape::unescape(&value)
+                        .map_or(value.clone(), |v| v.to_string());
+                    if let Some(unit) = &mut unit {
+                        unit.attributes
+                            .insert(format!("{}{}", PROP_PREFIX, name), value);
+                    }
+                }
+                b"tuv" => {
+                    language = match attribute(&element, "xml:lang")? {
+                        Some(language) => Some(language),
+                        None => attribute(&element, "lang")?,
+                    };
+                }
+                b"seg" => {
+                    let text = segment_text(&mut reader)?;
+                    if let Some(unit) = &mut unit {
+                        unit.variants
+                            .push((language.take().unwrap_or_default(), text));
+                    }
+                }
+                _ => {}
+            },
+            Event::End(element) if element.local_name().as_ref() == b"tu" => {
+                if let Some(unit) = unit.take() {
+                    segments.extend(unit_segments(unit, &source_language));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if version.is_none() {
+        bail!("not a TMX file");
+    }
+    Ok(segments)
+}
+
+// Texto de um `<seg>`. As marcações embutidas (`<ph>`, `<bpt>`...) guardam
+// código do formato original e ficam de fora; o texto dentro de `<hi>` e
+// `<sub>` é mantido.
+fn segment_text(reader: &mut Reader<&[u8]>) -> Result<String> {
+    let mut text = String::new();
+    let mut depth = 0;
+    // Profundidade da marcação ignorada em que a leitura está
+    let mut skipping: Option<usize> = None;
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => {
+                if skipping.is_none()
+                    && matches!(
+                        element.local_name().as_ref(),
+                        b"ph" | b"bpt" | b"ept" | b"it" | b"ut"
+                    )
+                {
+                    skipping = Some(depth);
+                }
+                depth += 1;
+            }
+            Event::End(_) if depth == 0 => break,
+            Event::End(_) => {
+                depth -= 1;
+                if skipping == Some(depth) {
+                    skipping = None;
+                }
+            }
+            Event::Text(content) if skipping.is_none() => text.push_str(&content.unescape()?),
+            Event::CData(content) if skipping.is_none() => {
+                text.push_str(&String::from_utf8_lossy(&content))
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(text)
+}
+
+fn unit_segments(mut unit: Unit, source_language: &str) -> Vec<Segment> {
+    let source_index = unit
+        .variants
+        .iter()
+        .position(|(language, _)| language.eq_ignore_ascii_case(source_language))
+        .unwrap_or(0);
+    if unit.variants.len() < 2 {
+        return Vec::new();
+    }
+    let (source_language, source) = unit.variants.remove(source_index);
+
+    let created = unit
+        .attributes
+        .remove("creationdate")
+        .and_then(|date| rfc3339_date(&date))
+        .unwrap_or_else(|| Local::now().to_rfc3339());
+    let key = unit
+        .attributes
+        .remove(&format!("{}{}", PROP_PREFIX, KEY_PROP))
+        .unwrap_or_default();
+    let file = unit
+        .attributes
+        .remove(&format!("{}{}", PROP_PREFIX, FILE_PROP))
+        .unwrap_or_default();
+
+    unit.variants
+        .into_iter()
+        .map(|(target_language, target)| Segment {
+            source: source.clone(),
+            target,
+            source_language: source_language.clone(),
+            target_language,
+            file: file.clone(),
+            key: key.clone(),
+            created: created.clone(),
+            attributes: unit.attributes.clone(),
+        })
+        .collect()
+}
@@ -0,0 +1,245 @@
+use anyhow::{Result, bail};
+use quick_xml::{
+    escape::{escape, unescape},
+    events::Event,
+    reader::Reader,
+};
+
+use super::{attribute, text_of};
+use crate::app::{Entry, EntryStatus};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Version {
+    V12,
+    V20,
+}
+
+impl Version {
+    pub fn parse(value: &str) -> Self {
+        if value.starts_with('2') {
+            Self::V20
+        } else {
+            Self::V12
+        }
+    }
+}
+
+// Unidade lida de um XLIFF devolvido: chave, tradução e estado, quando presentes
+pub struct XliffUnit {
+    pub key: String,
+    pub target: Option<String>,
+    pub status: Option<EntryStatus>,
+}
+
+pub struct Languages<'a> {
+    pub source: &'a str,
+    pub target: &'a str,
+}
+
+// Gera o XLIFF com origem, tradução, estado e notas de cada entrada. Entradas
+// ainda não traduzidas saem sem `<target>` para não enviar a origem como tradução.
+pub fn export(entries: &[Entry], original: &str, languages: Languages, version: Version) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    match version {
+        Version::V12 => {
+            out.push_str(
+                "<xliff version=\"1.2\" xmlns=\"urn:oasis:names:tc:xliff:document:1.2\">\n",
+            );
+            out.push_str(&format!(
+                "  <file original=\"{}\" source-language=\"{}\" target-language=\"{}\" datatype=\"plaintext\">\n    <body>\n",
+                escape(original),
+                escape(languages.source),
+                escape(languages.target)
+            ));
+        }
+        Version::V20 => {
+            out.push_str(&format!(
+                "<xliff xmlns=\"urn:oasis:names:tc:xliff:document:2.0\" version=\"2.0\" srcLang=\"{}\" trgLang=\"{}\">\n",
+                escape(languages.source),
+                escape(languages.target)
+            ));
+            out.push_str(&format!(
+                "  <file id=\"f1\" original=\"{}\">\n",
+                escape(original)
+            ));
+        }
+    }
+
+    for (index, entry) in entries.iter().enumerate() {
+        let key = escape(&entry.key);
+        let source = escape(text_of(&entry.original));
+        // Entradas bloqueadas saem com `translate="no"` e o texto de origem
+        let target = if entry.is_locked() {
+            Some(source.to_string())
+        } else {
+            entry
+                .has_translation()
+                .then(|| escape(text_of(&entry.translated)).to_string())
+        };
+        let translate = if entry.is_locked() {
+            " translate=\"no\""
+        } else {
+            ""
+        };
+
+        match version {
+            Version::V12 => {
+                out.push_str(&format!(
+                    "      <trans-unit id=\"{}\" resname=\"{}\"{}>\n        <source>{}</source>\n",
+                    key, key, translate, source
+                ));
+                if let Some(target) = target {
+                    out.push_str(&format!(
+                        "        <target state=\"{}\">{}</target>\n",
+                        state_v12(entry.status),
+                        target
+                    ));
+                }
+                for note in &entry.notes {
+                    out.push_str(&format!("        <note>{}</note>\n", escape(note)));
+                }
+                out.push_str("      </trans-unit>\n");
+            }
+            Version::V20 => {
+                // No 2.0 o `id` é um NMTOKEN; a chave vai no atributo `name`
+                out.push_str(&format!(
+                    "    <unit id=\"u{}\" name=\"{}\"{}>\n",
+                    index + 1,
+                    key,
+                    translate
+                ));
+                if !entry.notes.is_empty() {
+                    out.push_str("      <notes>\n");
+                    for note in &entry.notes {
+                        out.push_str(&format!("        <note>{}</note>\n", escape(note)));
+                    }
+                    out.push_str("      </notes>\n");
+                }
+                out.push_str(&format!(
+                    "      <segment state=\"{}\">\n        <source>{}</source>\n",
+                    state_v20(entry.status),
+                    source
+                ));
+                if let Some(target) = target {
+                    out.push_str(&format!("        <target>{}</target>\n", target));
+                }
+                out.push_str("      </segment>\n    </unit>\n");
+            }
+        }
+    }
+
+    match version {
+        Version::V12 => out.push_str("    </body>\n  </file>\n</xliff>\n"),
+        Version::V20 => out.push_str("  </file>\n</xliff>\n"),
+    }
+    out
+}
+
+fn state_v12(status: EntryStatus) -> &'static str {
+    match status {
+        EntryStatus::Untranslated => "new",
+        EntryStatus::NeedsReview => "needs-review-translation",
+        EntryStatus::Translated => "translated",
+        EntryStatus::Draft => "needs-translation",
+    }
+}
+
+fn state_v20(status: EntryStatus) -> &'static str {
+    match status {
+        EntryStatus::Untranslated | EntryStatus::Draft => "initial",
+        EntryStatus::NeedsReview => "translated",
+        EntryStatus::Translated => "final",
+    }
+}
+
+// No 2.0, `translated` ainda não passou por revisão
+fn parse_state(state: &str, version: Version) -> Option<EntryStatus> {
+    match (state, version) {
+        ("new" | "initial", _) => Some(EntryStatus::Untranslated),
+        ("translated", Version::V20) => Some(EntryStatus::NeedsReview),
+        ("needs-translation", _) => Some(EntryStatus::Draft),
+        ("translated" | "final" | "signed-off" | "reviewed", _) => Some(EntryStatus::Translated),
+        (s, _) if s.starts_with("needs-") => Some(EntryStatus::NeedsReview),
+        _ => None,
+    }
+}
+
+// Lê as unidades de um XLIFF 1.2 ou 2.0. A chave vem de `resname`/`name`, ou do
+// `id` quando esses atributos não existem.
+pub fn import(content: &str) -> Result<Vec<XliffUnit>> {
+    let mut reader = Reader::from_str(content);
+    let mut units = Vec::new();
+    let mut version = Version::V12;
+    let mut declared = None;
+    let mut current: Option<XliffUnit> = None;
+    // No 2.0 o estado fica no `<segment>`; no 1.2, no `<target>`
+    let mut segment_state: Option<String> = None;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) | Event::Empty(element)
+                if element.local_name().as_ref() == b"xliff" =>
+            {
+                declared = attribute(&element, "version")?;
+                version = Version::parse(declared.as_deref().unwrap_or_default());
+            }
+            Event::Start(element) => match element.local_name().as_ref() {
+                b"trans-unit" | b"unit" => {
+                    let key = match attribute(&element, "resname")? {
+                        Some(key) => Some(key),
+                        None => attribute(&element, "name")?,
+                    };
+                    let key = match key {
+                        Some(key) => key,
+                        None => attribute(&element, "id")?.unwrap_or_default(),
+                    };
+                    current = Some(XliffUnit {
+                        key,
+                        target: None,
+                        status: None,
+                    });
+                }
+                b"segment" => segment_state = attribute(&element, "state")?,
+                b"target" => {
+                    let state = attribute(&element, "state")?.or(segment_state.take());
+                    let span = reader.read_to_end(element.name())?;
+                    let raw = &content[span.start as usize..span.end as usize];
+                    let text = unescape(raw).map_or_else(|_| raw.to_string(), |t| t.to_string());
+                    if let Some(unit) = &mut current {
+                        // Unidades 2.0 com vários segmentos são unidas em um texto só
+                        unit.target.get_or_insert_with(String::new).push_str(&text);
+                        if let Some(status) = state.as_deref().and_then(|s| parse_state(s, version))
+                        {
+                            unit.status = Some(status);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::End(element)
+                if matches!(element.local_name().as_ref(), b"trans-unit" | b"unit") =>
+            {
+                if let Some(mut unit) = current.take() {
+                    if unit.status.is_none() {
+                        unit.status = segment_state
+                            .take()
+                            .as_deref()
+                            .and_then(|s| parse_state(s, version));
+                    }
+                    units.push(unit);
+                }
+                segment_state = None;
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !matches!(declared.as_deref(), Some("1.2") | Some("2.0") | Some("2.1")) {
+        bail!(
+            "unsupported XLIFF version: {}",
+            declared.unwrap_or_default()
+        );
+    }
+    Ok(units)
+}
@@ -0,0 +1,661 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::registry::{Document, Format, FormatContext};
+use super::{output_value, text_of};
+use crate::app::{Entry, EntryStatus, Lock};
+use crate::config::Config;
+use crate::file_operations;
+
+// YAML de origem aberto; a tradução vai para `output` com a mesma estrutura
+pub struct YamlDocument {
+    file: YamlFile,
+    output: PathBuf,
+}
+
+pub struct YamlFormat;
+
+impl Format for YamlFormat {
+    fn name(&self) -> &'static str {
+        "YAML"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["yml", "yaml"]
+    }
+
+    fn output_path(&self, path: &Path, context: &FormatContext) -> PathBuf {
+        let extension = path.extension().unwrap_or_default().to_string_lossy();
+        file_operations::output_path(path, context, &extension)
+    }
+
+    // As traduções são lidas do arquivo de saída, se ele já existir
+    fn load(&self, path: &Path, context: &FormatContext) -> Result<Option<(Vec<Entry>, Document)>> {
+        let config = context.config;
+        let file = parse(&fs::read_to_string(path)?, &config.source_language)?;
+        let output = self.output_path(path, context);
+        let existing = if output.exists() {
+            parse(&fs::read_to_string(&output)?, &config.target_language)?.values()
+        } else {
+            HashMap::new()
+        };
+
+        let mut entries = to_entries(&file, &existing);
+        file_operations::apply_sidecar(&mut entries, path, config)?;
+        Ok(Some((
+            entries,
+            Document::new(YamlDocument { file, output }),
+        )))
+    }
+
+    fn save(
+        &self,
+        document: &mut Document,
+        entries: &[Entry],
+        context: &FormatContext,
+    ) -> Result<()> {
+        let YamlDocument { file, output } = document.get_mut(self.name())?;
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        save(file, entries, context.config, output)
+    }
+}
+
+#[derive(Clone)]
+enum Style {
+    Plain,
+    Single,
+    Double,
+    // Bloco `|` ou `>`, com o recuo do conteúdo
+    Literal(usize),
+    Folded(usize),
+}
+
+// Um valor de texto editável. As linhas originais são mantidas para regravar
+// sem alteração o que não foi traduzido.
+struct Scalar {
+    path: Vec<String>,
+    indent: usize,
+    key: String,
+    style: Style,
+    value: String,
+    // Comentário no fim da linha (`  # ...`), preservado ao regravar
+    comment: String,
+    notes: Vec<String>,
+    raw: Vec<String>,
+}
+
+enum Line {
+    Raw(String),
+    Scalar(Scalar),
+}
+
+// Arquivo YAML de traduções. Apenas mapas aninhados com valores de texto são
+// editáveis; listas, âncoras e coleções inline são mantidas como estão.
+pub struct YamlFile {
+    lines: Vec<Line>,
+    // Linha da raiz de idioma, quando o arquivo segue o padrão Rails
+    root: Option<usize>,
+    trailing_newline: bool,
+}
+
+// `language` é o idioma do arquivo, reconhecido como raiz no padrão Rails
+pub fn parse(content: &str, language: &str) -> Result<YamlFile> {
+    let raw: Vec<&str> = content.lines().collect();
+    let mut lines = Vec::new();
+    let mut stack: Vec<(usize, String)> = Vec::new();
+    let mut comments: Vec<String> = Vec::new();
+    // (linha, nome, é um mapa) das chaves de nível zero
+    let mut top_level: Vec<(usize, String, bool)> = Vec::new();
+
+    let mut i = 0;
+    while i < raw.len() {
+        let line = raw[i];
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+
+        if trimmed.is_empty() {
+            comments.clear();
+            lines.push(Line::Raw(line.to_string()));
+            i += 1;
+            continue;
+        }
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            comments.push(comment.trim().to_string());
+            lines.push(Line::Raw(line.to_string()));
+            i += 1;
+            continue;
+        }
+
+        while stack.last().is_some_and(|(level, _)| *level >= indent) {
+            stack.pop();
+        }
+
+        let Some((key, name, rest)) = split_key(trimmed) else {
+            // Documento (`---`), item de lista solto ou conteúdo não suportado
+            comments.clear();
+            let end = skip_nested(&raw, i + 1, indent);
+            lines.extend(raw[i..end].iter().map(|l| Line::Raw(l.to_string())));
+            i = end;
+            continue;
+        };
+        let notes = std::mem::take(&mut comments);
+        let value = rest.trim_start();
+
+        if value.is_empty() || value.starts_with('#') {
+            if indent == 0 {
+                top_level.push((lines.len(), name.clone(), true));
+            }
+            // Uma lista pode começar no mesmo recuo da chave
+            let next = raw[i + 1..]
+                .iter()
+                .map(|l| l.trim_start())
+                .find(|l| !l.is_empty() && !l.starts_with('#'));
+            if next.is_some_and(|l| l == "-" || l.starts_with("- ")) {
+                let mut end = i + 1;
+                while end < raw.len() {
+                    let next = raw[end].trim_start();
+                    let next_indent = raw[end].len() - next.len();
+                    let item = next == "-" || next.starts_with("- ");
+                    if next.is_empty() || next_indent > indent || (next_indent == indent && item) {
+                        end += 1;
+                    } else {
+                        break;
+                    }
+                }
+                lines.extend(raw[i..end].iter().map(|l| Line::Raw(l.to_string())));
+                i = end;
+            } else {
+                stack.push((indent, name));
+                lines.push(Line::Raw(line.to_string()));
+                i += 1;
+            }
+            continue;
+        }
+
+        if indent == 0 {
+            top_level.push((lines.len(), name.clone(), false));
+        }
+        let mut path: Vec<String> = stack.iter().map(|(_, n)| n.clone()).collect();
+        path.push(name);
+
+        let scalar = if value.starts_with('|') || value.starts_with('>') {
+            let (header, comment) = split_comment(value);
+            let mut end = i + 1;
+            while end < raw.len() && (raw[end].trim().is_empty() || indent_of(raw[end]) > indent) {
+                end += 1;
+            }
+            // Linhas em branco no fim pertencem ao que vem depois do bloco
+            let keep = header.contains('+');
+            while !keep && end > i + 1 && raw[end - 1].trim().is_empty() {
+                end -= 1;
+            }
+            let body = &raw[i + 1..end];
+            let explicit = header
+                .chars()
+                .find_map(|c| c.to_digit(10))
+                .map(|n| indent + n as usize);
+            let content_indent = explicit.unwrap_or_else(|| {
+                body.iter()
+                    .find(|l| !l.trim().is_empty())
+                    .map(|l| indent_of(l))
+                    .unwrap_or(indent + 2)
+            });
+            let body: Vec<&str> = body
+                .iter()
+                .map(|l| l.get(content_indent..).unwrap_or(""))
+                .collect();
+            let folded = header.starts_with('>');
+            let value = block_value(&body, folded, header);
+            let style = if folded {
+                Style::Folded(content_indent)
+            } else {
+                Style::Literal(content_indent)
+            };
+            Some((style, value, comment.to_string(), end))
+        } else if let Some(quoted) = value.strip_prefix('"') {
+            double_quoted(quoted).map(|(value, tail)| (Style::Double, value, tail, i + 1))
+        } else if let Some(quoted) = value.strip_prefix('\'') {
+            single_quoted(quoted).map(|(value, tail)| (Style::Single, value, tail, i + 1))
+        } else if value.starts_with(['[', '{', '&', '*', '!', '%', '@', '`']) {
+            None
+        } else {
+            let (text, comment) = split_comment(value);
+            Some((Style::Plain, text.to_string(), comment.to_string(), i + 1))
+        };
+
+        // Continuação em outra linha (texto multilinha sem bloco) não é editável
+        let continued = scalar
+            .as_ref()
+            .is_some_and(|(_, _, _, end)| skip_nested(&raw, *end, indent) > *end);
+
+        match scalar {
+            Some((style, value, comment, end)) if !continued => {
+                lines.push(Line::Scalar(Scalar {
+                    path,
+                    indent,
+                    key,
+                    style,
+                    value,
+                    comment,
+                    notes,
+                    raw: raw[i..end].iter().map(|l| l.to_string()).collect(),
+                }));
+                i = end;
+            }
+            _ => {
+                let end = skip_nested(&raw, i + 1, indent);
+                lines.extend(raw[i..end].iter().map(|l| Line::Raw(l.to_string())));
+                i = end;
+            }
+        }
+    }
+
+    // Raiz no estilo Rails (`en:`, `pt-BR:`). Só o idioma do arquivo conta, para
+    // que uma chave única como `app:` continue fazendo parte das chaves.
+    let root = match top_level.as_slice() {
+        [(line, name, true)] if same_language(name, language) => Some(*line),
+        _ => None,
+    };
+
+    Ok(YamlFile {
+        lines,
+        root,
+        trailing_newline: content.ends_with('\n'),
+    })
+}
+
+// `pt_BR` e `pt-br` são o mesmo idioma
+fn same_language(a: &str, b: &str) -> bool {
+    let normalize = |code: &str| code.to_lowercase().replace('_', "-");
+    normalize(a) == normalize(b)
+}
+
+impl YamlFile {
+    fn scalars(&self) -> impl Iterator<Item = &Scalar> {
+        self.lines.iter().filter_map(|line| match line {
+            Line::Scalar(scalar) => Some(scalar),
+            Line::Raw(_) => None,
+        })
+    }
+
+    // Chave da entrada: caminho com `.`, sem a raiz de idioma
+    fn key_of(&self, scalar: &Scalar) -> String {
+        let skip = usize::from(self.root.is_some());
+        scalar.path[skip..].join(".")
+    }
+
+    pub fn values(&self) -> HashMap<String, String> {
+        self.scalars()
+            .map(|s| (self.key_of(s), s.value.clone()))
+            .collect()
+    }
+}
+
+// Entradas na ordem do arquivo, com a tradução existente quando houver
+pub fn to_entries(file: &YamlFile, existing: &HashMap<String, String>) -> Vec<Entry> {
+    file.scalars()
+        .map(|scalar| {
+            let key = file.key_of(scalar);
+            let translated = existing
+                .get(&key)
+                .cloned()
+                .map_or(Value::Null, Value::String);
+            Entry {
+                key,
+                original: Value::String(scalar.value.clone()),
+                translated,
+                status: EntryStatus::Untranslated,
+                lock: Lock::Unlocked,
+                max_length: None,
+                dirty: false,
+                modified: None,
+                notes: scalar.notes.clone(),
+            }
+        })
+        .collect()
+}
+
+// Grava a estrutura do arquivo de origem com os valores traduzidos, trocando a
+// raiz de idioma pelo código de destino. Chaves omitidas por
+// `untranslated_output` perdem as suas linhas.
+pub fn save(file: &YamlFile, entries: &[Entry], config: &Config, path: &Path) -> Result<()> {
+    let mut output = Vec::new();
+    let mut entries = entries.iter();
+
+    for (index, line) in file.lines.iter().enumerate() {
+        match line {
+            // Só o nome da raiz muda; um comentário na mesma linha é mantido
+            Line::Raw(text) if file.root == Some(index) => match split_key(text) {
+                Some((_, _, rest)) => output.push(format!("{}:{}", config.target_language, rest)),
+                None => output.push(text.clone()),
+            },
+            Line::Raw(text) => output.push(text.clone()),
+            Line::Scalar(scalar) => {
+                let value = match entries.next() {
+                    Some(entry) => match output_value(entry, config) {
+                        Some(value) => text_of(&value),
+                        None => continue,
+                    },
+                    None => scalar.value.clone(),
+                };
+                if value == scalar.value {
+                    output.extend(scalar.raw.iter().cloned());
+                } else {
+                    output.extend(render(scalar, &value));
+                }
+            }
+        }
+    }
+
+    let mut content = output.join("\n");
+    if file.trailing_newline {
+        content.push('\n');
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+fn render(scalar: &Scalar, value: &str) -> Vec<String> {
+    let prefix = format!("{}{}:", " ".repeat(scalar.indent), scalar.key);
+    let comment = &scalar.comment;
+
+    match scalar.style {
+        Style::Literal(content_indent) | Style::Folded(content_indent) => {
+            let folded = matches!(scalar.style, Style::Folded(_));
+            let body = value.trim_end_matches('\n');
+            let chomp = match value.len() - body.len() {
+                0 => "-",
+                1 => "",
+                _ => "+",
+            };
+            // Indicador de recuo explícito quando o texto começa com espaço
+            let explicit = if body.starts_with(' ') {
+                (content_indent - scalar.indent).to_string()
+            } else {
+                String::new()
+            };
+            let indicator = if folded { '>' } else { '|' };
+            let mut lines = vec![format!(
+                "{} {}{}{}{}",
+                prefix, indicator, explicit, chomp, comment
+            )];
+            let pad = " ".repeat(content_indent);
+            for (n, text) in body.split('\n').enumerate() {
+                // No estilo dobrado, uma quebra de linha é uma linha em branco
+                if folded && n > 0 {
+                    lines.push(String::new());
+                }
+                if text.is_empty() {
+                    lines.push(String::new());
+                } else {
+                    lines.push(format!("{}{}", pad, text));
+                }
+            }
+            for _ in 1..value.len() - body.len() {
+                lines.push(String::new());
+            }
+            lines
+        }
+        Style::Plain if !needs_quotes(value) => vec![format!("{} {}{}", prefix, value, comment)],
+        Style::Single if !value.contains(['\n', '\t']) => {
+            vec![format!(
+                "{} '{}'{}",
+                prefix,
+                value.replace('\'', "''"),
+                comment
+            )]
+        }
+        _ => vec![format!("{} \"{}\"{}", prefix, escape(value), comment)],
+    }
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+// Primeira linha, a partir de `start`, que não pertence ao item com recuo `indent`
+fn skip_nested(raw: &[&str], start: usize, indent: usize) -> usize {
+    let mut end = start;
+    while end < raw.len() && (raw[end].trim().is_empty() || indent_of(raw[end]) > indent) {
+        end += 1;
+    }
+    while end > start && raw[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+    end
+}
+
+// Separa `chave: resto`, retornando a chave como escrita e o nome sem aspas
+fn split_key(text: &str) -> Option<(String, String, &str)> {
+    if text.starts_with('-') && (text.len() == 1 || text[1..].starts_with(' ')) {
+        return None;
+    }
+    let (name, after) = if let Some(quoted) = text.strip_prefix('"') {
+        let (name, tail) = double_quoted(quoted)?;
+        (name, text.len() - tail.len())
+    } else if let Some(quoted) = text.strip_prefix('\'') {
+        let (name, tail) = single_quoted(quoted)?;
+        (name, text.len() - tail.len())
+    } else {
+        let colon = text
+            .char_indices()
+            .find(|&(i, c)| c == ':' && text[i + 1..].chars().next().is_none_or(|n| n == ' '))
+            .map(|(i, _)| i)?;
+        let name = text[..colon].trim_end();
+        if name.is_empty() || name.starts_with(['?', '[', '{', '#']) {
+            return None;
+        }
+        (name.to_string(), colon)
+    };
+
+    let rest = text[after..].strip_prefix(':')?;
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((text[..after].to_string(), name, rest))
+}
+
+// Separa o comentário do fim da linha, mantendo o espaço antes do `#`
+fn split_comment(text: &str) -> (&str, &str) {
+    match text.find(" #") {
+        Some(pos) => {
+            let value = text[..pos].trim_end();
+            (value, &text[value.len()..])
+        }
+        None => (text.trim_end(), ""),
+    }
+}
+
+// Lê o conteúdo entre aspas duplas e retorna o texto e o que vem depois delas
+fn double_quoted(text: &str) -> Option<(String, String)> {
+    let mut value = String::new();
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, text[i + 1..].to_string())),
+            '\\' => {
+                let (_, escaped) = chars.next()?;
+                match escaped {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    'r' => value.push('\r'),
+                    '0' => value.push('\0'),
+                    'x' | 'u' | 'U' => {
+                        let digits = match escaped {
+                            'x' => 2,
+                            'u' => 4,
+                            _ => 8,
+                        };
+                        let hex: String = (0..digits)
+                            .filter_map(|_| chars.next())
+                            .map(|(_, c)| c)
+                            .collect();
+                        value.push(
+                            u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)?,
+                        );
+                    }
+                    other => value.push(other),
+                }
+            }
+            other => value.push(other),
+        }
+    }
+    None
+}
+
+fn single_quoted(text: &str) -> Option<(String, String)> {
+    let mut value = String::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '\'' {
+            if chars.peek().is_some_and(|&(_, n)| n == '\'') {
+                chars.next();
+                value.push('\'');
+            } else {
+                return Some((value, text[i + 1..].to_string()));
+            }
+        } else {
+            value.push(c);
+        }
+    }
+    None
+}
+
+// Valor de um bloco `|`/`>` conforme o indicador de quebra final (`-`, `+`)
+fn block_value(body: &[&str], folded: bool, header: &str) -> String {
+    let mut text = String::new();
+    for (n, line) in body.iter().enumerate() {
+        if n > 0 {
+            let joined = folded && !line.is_empty() && !body[n - 1].is_empty();
+            text.push(if joined { ' ' } else { '\n' });
+        }
+        text.push_str(line);
+    }
+    if folded {
+        // Uma linha em branco entre parágrafos representa uma única quebra
+        text = text.replace("\n\n", "\n");
+    }
+
+    let content = text.trim_end_matches('\n');
+    if header.contains('-') || content.is_empty() {
+        content.to_string()
+    } else if header.contains('+') {
+        format!("{}\n", text)
+    } else {
+        format!("{}\n", content)
+    }
+}
+
+fn needs_quotes(value: &str) -> bool {
+    const RESERVED: [&str; 11] = [
+        "true", "false", "yes", "no", "on", "off", "null", "~", "y", "n", "",
+    ];
+    value != value.trim()
+        || value.contains(['\n', '\t', '"'])
+        || value.contains(": ")
+        || value.contains(" #")
+        || value.ends_with(':')
+        || value.starts_with([
+            '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '%', '@',
+            '`',
+        ])
+        || RESERVED.contains(&value.to_lowercase().as_str())
+        || value.parse::<f64>().is_ok()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Lê `tests/fixtures/yaml/<nome>` com origem em inglês, traduz as chaves
+    // indicadas e retorna as chaves na ordem do arquivo e o texto gravado
+    fn translate(name: &str, translations: &[(&str, &str)]) -> (Vec<String>, String) {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/yaml");
+        let file = parse(&fs::read_to_string(fixtures.join(name)).unwrap(), "en").unwrap();
+        let mut entries = to_entries(&file, &HashMap::new());
+        for (key, text) in translations {
+            let entry = entries
+                .iter_mut()
+                .find(|entry| entry.key == *key)
+                .unwrap_or_else(|| panic!("missing key {}", key));
+            entry.translated = Value::String(text.to_string());
+            entry.status = EntryStatus::Translated;
+        }
+
+        let dir = std::env::temp_dir().join("transtui-yaml");
+        fs::create_dir_all(&dir).unwrap();
+        let output = dir.join(name);
+        save(&file, &entries, &Config::default(), &output).unwrap();
+        let keys = entries.into_iter().map(|entry| entry.key).collect();
+        (keys, fs::read_to_string(output).unwrap())
+    }
+
+    fn expected(name: &str) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/yaml/expected");
+        fs::read_to_string(path.join(name)).unwrap()
+    }
+
+    #[test]
+    fn rails_root_is_renamed_keeping_its_comment() {
+        let (keys, output) = translate("rails.yml", &[("greeting", "Olá")]);
+        assert_eq!(keys, ["greeting", "nav.home"]);
+        assert_eq!(output, expected("rails.yml"));
+    }
+
+    #[test]
+    fn single_root_that_is_not_the_source_language_is_a_key() {
+        let (keys, output) = translate("single_root.yml", &[("app.title", "Loja")]);
+        assert_eq!(keys, ["app.title", "app.checkout"]);
+        assert_eq!(output, expected("single_root.yml"));
+    }
+
+    #[test]
+    fn block_scalars_keep_their_style() {
+        let (keys, output) = translate(
+            "blocks.yml",
+            &[
+                ("terms", "Primeira linha\nSegunda linha\n"),
+                ("summary", "Texto dobrado continua aqui"),
+            ],
+        );
+        assert_eq!(keys, ["terms", "summary", "footer"]);
+        assert_eq!(output, expected("blocks.yml"));
+    }
+
+    #[test]
+    fn keys_keep_the_source_order() {
+        let (keys, output) = translate("order.yml", &[("mango", "Manga"), ("zebra", "Zebra")]);
+        assert_eq!(keys, ["zebra", "apple", "mango"]);
+        assert_eq!(output, expected("order.yml"));
+    }
+
+    #[test]
+    fn root_matches_the_language_ignoring_case_and_separator() {
+        let file = parse("pt_br:\n  title: Início\n", "pt-BR").unwrap();
+        assert_eq!(file.values().keys().collect::<Vec<_>>(), ["title"]);
+    }
+}
@@ -0,0 +1,228 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::formats::spreadsheet;
+
+// Termo do glossário: as traduções aceitas (basta usar uma) e as proibidas.
+// Sem `source`, as proibidas valem para qualquer tradução.
+pub struct Term {
+    pub source: String,
+    pub targets: Vec<String>,
+    pub forbidden: Vec<String>,
+    pub note: Option<String>,
+    pattern: Option<Regex>,
+    target_patterns: Vec<Regex>,
+    forbidden_patterns: Vec<Regex>,
+}
+
+#[derive(Default)]
+pub struct Glossary {
+    terms: Vec<Term>,
+}
+
+// Formato TOML: uma tabela `[[terms]]` por termo
+#[derive(Deserialize)]
+struct GlossaryFile {
+    #[serde(default)]
+    terms: Vec<TermEntry>,
+}
+
+#[derive(Deserialize)]
+struct TermEntry {
+    #[serde(default)]
+    source: String,
+    #[serde(default)]
+    target: OneOrMany,
+    #[serde(default)]
+    forbidden: OneOrMany,
+    note: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(untagged)]
+enum OneOrMany {
+    #[default]
+    None,
+    One(String),
+    Many(Vec<String>),
+}
+
+impl From<OneOrMany> for Vec<String> {
+    fn from(value: OneOrMany) -> Self {
+        match value {
+            OneOrMany::None => Vec::new(),
+            OneOrMany::One(text) => vec![text],
+            OneOrMany::Many(list) => list,
+        }
+    }
+}
+
+impl Glossary {
+    // Lê o glossário de `glossary_path`: `.csv`/`.tsv` com as colunas
+    // `source`, `target`, `forbidden` e `note` (várias opções separadas por `|`),
+    // ou TOML nos demais casos
+    pub fn load(config: &Config) -> Result<Self> {
+        let Some(path) = &config.glossary_path else {
+            return Ok(Self::default());
+        };
+        let path = Path::new(path);
+        let content =
+            fs::read_to_string(path).with_context(|| format!("glossary {}", path.display()))?;
+        let extension = path.extension().unwrap_or_default();
+        let entries = if extension == "csv" || extension == "tsv" {
+            read_csv(&content, spreadsheet::delimiter(path))
+        } else {
+            toml::from_str::<GlossaryFile>(&content)
+                .map(|file| file.terms)
+                .map_err(Into::into)
+        }
+        .with_context(|| format!("invalid glossary {}", path.display()))?;
+
+        let terms = entries
+            .into_iter()
+            .map(|entry| {
+                let targets: Vec<String> = entry.target.into();
+                let forbidden: Vec<String> = entry.forbidden.into();
+                Ok(Term {
+                    pattern: (!entry.source.trim().is_empty())
+                        .then(|| word_pattern(&entry.source))
+                        .transpose()?,
+                    target_patterns: targets
+                        .iter()
+                        .map(|t| word_pattern(t))
+                        .collect::<Result<_>>()?,
+                    forbidden_patterns: forbidden
+                        .iter()
+                        .map(|t| word_pattern(t))
+                        .collect::<Result<_>>()?,
+                    source: entry.source,
+                    targets,
+                    forbidden,
+                    note: entry.note.filter(|note| !note.is_empty()),
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { terms })
+    }
+
+    // Termos do glossário que aparecem no texto de origem
+    pub fn terms_in(&self, original: &str) -> Vec<&Term> {
+        self.terms
+            .iter()
+            .filter(|term| term.pattern.as_ref().is_some_and(|p| p.is_match(original)))
+            .collect()
+    }
+
+    // Trechos do texto de origem com termos do glossário, em ordem e sem sobreposição
+    pub fn highlights(&self, original: &str) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = self
+            .terms
+            .iter()
+            .filter_map(|term| term.pattern.as_ref())
+            .flat_map(|pattern| pattern.find_iter(original).map(|m| m.range()))
+            .collect();
+        ranges.sort_by_key(|range| (range.start, usize::MAX - range.end));
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start < last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+
+    // Termos obrigatórios ausentes da tradução e termos proibidos encontrados nela
+    pub fn check(&self, original: &str, translated: &str) -> (Vec<&Term>, Vec<&str>) {
+        let mut missing = Vec::new();
+        let mut forbidden = Vec::new();
+        for term in &self.terms {
+            let applies = match &term.pattern {
+                Some(pattern) => pattern.is_match(original),
+                None => true,
+            };
+            if !applies {
+                continue;
+            }
+            if term.pattern.is_some()
+                && !term.target_patterns.is_empty()
+                && !term.target_patterns.iter().any(|p| p.is_match(translated))
+            {
+                missing.push(term);
+            }
+            for (word, pattern) in term.forbidden.iter().zip(&term.forbidden_patterns) {
+                if pattern.is_match(translated) && !forbidden.contains(&word.as_str()) {
+                    forbidden.push(word.as_str());
+                }
+            }
+        }
+        (missing, forbidden)
+    }
+}
+
+fn read_csv(content: &str, delimiter: u8) -> Result<Vec<TermEntry>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(content.trim_start_matches('\u{feff}').as_bytes());
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim().eq_ignore_ascii_case(name))
+    };
+    let (source, target, forbidden, note) = (
+        column("source"),
+        column("target"),
+        column("forbidden"),
+        column("note"),
+    );
+
+    let mut entries = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let cell = |column: Option<usize>| column.and_then(|c| record.get(c)).unwrap_or("").trim();
+        let list = |column: Option<usize>| {
+            OneOrMany::Many(
+                cell(column)
+                    .split('|')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            )
+        };
+        entries.push(TermEntry {
+            source: cell(source).to_string(),
+            target: list(target),
+            forbidden: list(forbidden),
+            note: Some(cell(note).to_string()),
+        });
+    }
+    Ok(entries)
+}
+
+// Busca sem diferenciar maiúsculas, em palavras inteiras quando o termo começa
+// e termina com letras ou números
+fn word_pattern(term: &str) -> Result<Regex> {
+    let term = term.trim();
+    let edge = |c: Option<char>| {
+        if c.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            r"\b"
+        } else {
+            ""
+        }
+    };
+    let pattern = format!(
+        "(?i){}{}{}",
+        edge(term.chars().next()),
+        regex::escape(term),
+        edge(term.chars().last())
+    );
+    Ok(Regex::new(&pattern)?)
+}
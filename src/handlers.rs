@@ -1,22 +1,31 @@
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use chrono::Local;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use serde_json::Value;
-use std::fs;
+use std::rc::Rc;
 
-use crate::app::{App, AppState};
+use crate::app::{App, AppState, EditingState, Entry, EntryStatus, Filter};
 use crate::file_operations;
-use crate::ui::format_json_value;
+use crate::find_replace::{ReplaceField, ReplaceState};
+use crate::formats::registry::{self, FormatContext};
+use crate::validation;
 
 pub fn handle_events(app: &mut App, key: KeyEvent) -> Result<()> {
     match app.state {
-        AppState::FileSelection => handle_file_selection(app, key),
-        AppState::Editing => handle_editing(app, key),
-        AppState::SaveConfirmation => handle_save_confirmation(app, key),
-        AppState::Exiting => Ok(()),
+        AppState::FileSelection => handle_file_selection(app, key)?,
+        AppState::Editing => handle_editing(app, key)?,
+        AppState::SaveConfirmation => handle_save_confirmation(app, key)?,
+        AppState::Memory => handle_memory(app, key)?,
+        AppState::Exiting => {}
     }
+    if app.state == AppState::Editing {
+        app.update_memory_matches();
+    }
+    Ok(())
 }
 
 fn handle_file_selection(app: &mut App, key: KeyEvent) -> Result<()> {
+    app.file_selection.message = None;
     match key.code {
         KeyCode::Char('q') | KeyCode::Char('Q') => app.state = AppState::Exiting,
         KeyCode::Up => {
@@ -34,72 +43,25 @@ fn handle_file_selection(app: &mut App, key: KeyEvent) -> Result<()> {
             }
         }
         KeyCode::Enter => {
-            if let Some(file_path) = app.get_selected_file_path() {
-                let content = fs::read_to_string(file_path)?;
-                let data: Value = serde_json::from_str(&content)?;
-
-                if let Value::Object(original_map) = data {
-                    let existing_translations = file_operations::load_existing_translations(
-                        file_path,
-                        app.locale.get("translations_folder"),
-                        app.locale.get("translation_suffix"),
-                    )?;
-
-                    let toml_path = file_path.with_extension("toml");
-                    let translated_keys = file_operations::load_translated_keys(&toml_path)?;
-
-                    let mut translated_count = 0;
-                    let entries = original_map
-                        .clone()
-                        .into_iter()
-                        .map(|(key, original_value)| {
-                            let is_translated = translated_keys.contains(&key);
-                            if is_translated {
-                                translated_count += 1;
-                            }
-
-                            let translated = if let Some(trans) = existing_translations.get(&key) {
-                                trans.clone()
-                            } else {
-                                original_value.clone()
-                            };
-
-                            crate::app::Entry {
-                                key: key.clone(),
-                                original: original_value,
-                                translated,
-                                is_translated,
-                            }
-                        })
-                        .collect();
-
-                    let total_keys = original_map.len();
-
-                    let mut table_state = tui::widgets::TableState::default();
-                    table_state.select(Some(0));
-
-                    app.editing = Some(crate::app::EditingState {
-                        entries,
-                        table_state,
-                        original_path: file_path.to_path_buf(),
-                        editing: None,
-                        input: String::new(),
-                        cursor_pos: 0,
-                        search_query: String::new(),
-                        search_mode: false,
-                        search_results: Vec::new(),
-                        search_selection: None,
-                        total_keys,
-                        translated_keys: translated_count,
-                        save_notification: None,
-                    });
-                    app.state = AppState::Editing;
-                }
+            if let Some(file_path) = app.get_selected_file_path()
+                && let Some(format) = registry::find(file_path)
+                && let Some((entries, document)) =
+                    format.load(file_path, &FormatContext::new(&app.locale, &app.config))?
+            {
+                app.editing = Some(EditingState::new(
+                    file_path.to_path_buf(),
+                    entries,
+                    document,
+                    format,
+                    Rc::clone(&app.glossary),
+                ));
+                app.state = AppState::Editing;
             }
         }
         KeyCode::F(2) => {
             app.switch_language()?;
         }
+        KeyCode::Char('m') | KeyCode::Char('M') => app.open_memory_screen(),
         KeyCode::Esc => app.state = AppState::Exiting,
         _ => {}
     }
@@ -107,14 +69,27 @@ fn handle_file_selection(app: &mut App, key: KeyEvent) -> Result<()> {
 }
 
 fn handle_editing(app: &mut App, key: KeyEvent) -> Result<()> {
+    if app.editing.as_ref().is_some_and(|s| s.review.is_some()) {
+        return handle_review(app, key);
+    }
+    if app.editing.as_ref().is_some_and(|s| s.replace.is_some()) {
+        return handle_replace(app, key);
+    }
+    if app.editing.as_ref().is_some_and(|s| s.filter_mode) {
+        return handle_filter(app, key);
+    }
+    if app.editing.as_ref().is_some_and(|s| s.goto_mode) {
+        return handle_goto(app, key);
+    }
+
     if let Some(state) = &mut app.editing {
         if state.search_mode {
             match key.code {
                 KeyCode::Enter => {
-                    if let Some(selected) = state.search_selection {
-                        if let Some(&entry_index) = state.search_results.get(selected) {
-                            state.table_state.select(Some(entry_index));
-                        }
+                    if let Some(selected) = state.search_selection
+                        && let Some(&entry_index) = state.search_results.get(selected)
+                    {
+                        state.select_entry(entry_index);
                     }
                     state.search_mode = false;
                     state.search_query.clear();
@@ -127,27 +102,23 @@ fn handle_editing(app: &mut App, key: KeyEvent) -> Result<()> {
                     state.search_results.clear();
                     state.search_selection = None;
                 }
-                KeyCode::Up => {
-                    if !state.search_results.is_empty() {
-                        let new_selection = match state.search_selection {
-                            Some(current) if current > 0 => Some(current - 1),
-                            None => Some(state.search_results.len() - 1),
-                            _ => None,
-                        };
-                        state.search_selection = new_selection;
-                    }
+                KeyCode::Up if !state.search_results.is_empty() => {
+                    let new_selection = match state.search_selection {
+                        Some(current) if current > 0 => Some(current - 1),
+                        None => Some(state.search_results.len() - 1),
+                        _ => None,
+                    };
+                    state.search_selection = new_selection;
                 }
-                KeyCode::Down => {
-                    if !state.search_results.is_empty() {
-                        let new_selection = match state.search_selection {
-                            Some(current) if current < state.search_results.len() - 1 => {
-                                Some(current + 1)
-                            }
-                            None => Some(0),
-                            _ => None,
-                        };
-                        state.search_selection = new_selection;
-                    }
+                KeyCode::Down if !state.search_results.is_empty() => {
+                    let new_selection = match state.search_selection {
+                        Some(current) if current < state.search_results.len() - 1 => {
+                            Some(current + 1)
+                        }
+                        None => Some(0),
+                        _ => None,
+                    };
+                    state.search_selection = new_selection;
                 }
                 KeyCode::Char(c) => {
                     state.search_query.push(c);
@@ -165,32 +136,39 @@ fn handle_editing(app: &mut App, key: KeyEvent) -> Result<()> {
         if let Some(editing_index) = state.editing {
             match key.code {
                 KeyCode::Enter => {
-                    if let Some(entry) = state.entries.get_mut(editing_index) {
-                        let value = if state.input.is_empty() {
-                            Value::String("".to_string())
-                        } else {
-                            Value::String(state.input.clone())
-                        };
-                        entry.translated = value;
+                    let machine_input = state.machine_input.take();
+                    if editing_index < state.entries.len() {
+                        state.apply_input(editing_index);
+                        let entry = &mut state.entries[editing_index];
+                        // Tradução automática aceita como veio fica como rascunho
+                        if machine_input.as_deref() == Some(state.input.as_str())
+                            && entry.status != EntryStatus::Draft
+                        {
+                            entry.status = EntryStatus::Draft;
+                            entry.modified = Some(Local::now());
+                            state.recount_translated();
+                            file_operations::save_translated_keys(
+                                &state.sidecar_path(),
+                                &state.entries,
+                            )?;
+                        }
                     }
                     state.editing = None;
                     state.input.clear();
                     state.cursor_pos = 0;
+                    state.refresh_view();
                 }
                 KeyCode::Esc => {
                     state.editing = None;
+                    state.machine_input = None;
                     state.input.clear();
                     state.cursor_pos = 0;
                 }
-                KeyCode::Left => {
-                    if state.cursor_pos > 0 {
-                        state.cursor_pos -= 1;
-                    }
+                KeyCode::Left if state.cursor_pos > 0 => {
+                    state.cursor_pos -= 1;
                 }
-                KeyCode::Right => {
-                    if state.cursor_pos < state.input.chars().count() {
-                        state.cursor_pos += 1;
-                    }
+                KeyCode::Right if state.cursor_pos < state.input.chars().count() => {
+                    state.cursor_pos += 1;
                 }
                 KeyCode::Char(c) => {
                     let byte_pos: usize = state
@@ -202,44 +180,40 @@ fn handle_editing(app: &mut App, key: KeyEvent) -> Result<()> {
                     state.input.insert(byte_pos, c);
                     state.cursor_pos += 1;
                 }
-                KeyCode::Backspace => {
-                    if state.cursor_pos > 0 {
-                        let byte_start: usize = state
+                KeyCode::Backspace if state.cursor_pos > 0 => {
+                    let byte_start: usize = state
+                        .input
+                        .chars()
+                        .take(state.cursor_pos - 1)
+                        .map(|c| c.len_utf8())
+                        .sum();
+                    let byte_end: usize = byte_start
+                        + state
                             .input
                             .chars()
-                            .take(state.cursor_pos - 1)
+                            .nth(state.cursor_pos - 1)
                             .map(|c| c.len_utf8())
-                            .sum();
-                        let byte_end: usize = byte_start
-                            + state
-                                .input
-                                .chars()
-                                .nth(state.cursor_pos - 1)
-                                .map(|c| c.len_utf8())
-                                .unwrap_or(0);
+                            .unwrap_or(0);
 
-                        state.input.drain(byte_start..byte_end);
-                        state.cursor_pos -= 1;
-                    }
+                    state.input.drain(byte_start..byte_end);
+                    state.cursor_pos -= 1;
                 }
-                KeyCode::Delete => {
-                    if state.cursor_pos < state.input.chars().count() {
-                        let byte_start: usize = state
+                KeyCode::Delete if state.cursor_pos < state.input.chars().count() => {
+                    let byte_start: usize = state
+                        .input
+                        .chars()
+                        .take(state.cursor_pos)
+                        .map(|c| c.len_utf8())
+                        .sum();
+                    let byte_end: usize = byte_start
+                        + state
                             .input
                             .chars()
-                            .take(state.cursor_pos)
+                            .nth(state.cursor_pos)
                             .map(|c| c.len_utf8())
-                            .sum();
-                        let byte_end: usize = byte_start
-                            + state
-                                .input
-                                .chars()
-                                .nth(state.cursor_pos)
-                                .map(|c| c.len_utf8())
-                                .unwrap_or(0);
+                            .unwrap_or(0);
 
-                        state.input.drain(byte_start..byte_end);
-                    }
+                    state.input.drain(byte_start..byte_end);
                 }
                 KeyCode::Home => state.cursor_pos = 0,
                 KeyCode::End => state.cursor_pos = state.input.chars().count(),
@@ -253,6 +227,12 @@ fn handle_editing(app: &mut App, key: KeyEvent) -> Result<()> {
                 KeyCode::Char('b') | KeyCode::Char('B') => {
                     app.save_current_file()?;
                 }
+                KeyCode::Char('r') | KeyCode::Char('R') => {
+                    state.replace = Some(ReplaceState::new());
+                }
+                KeyCode::Char('u') | KeyCode::Char('U') => {
+                    app.undo()?;
+                }
                 KeyCode::Char('s') | KeyCode::Char('S') => {
                     state.search_mode = true;
                     state.search_query.clear();
@@ -265,7 +245,79 @@ fn handle_editing(app: &mut App, key: KeyEvent) -> Result<()> {
                     });
                     app.state = AppState::SaveConfirmation;
                 }
-                KeyCode::Up => {
+                KeyCode::Char('f') | KeyCode::Char('F') => {
+                    state.filter_mode = true;
+                }
+                KeyCode::Char('v') | KeyCode::Char('V') => {
+                    app.toggle_tree_mode();
+                }
+                KeyCode::Left if state.tree_mode => state.collapse_or_parent(),
+                KeyCode::Right if state.tree_mode => {
+                    if let Some(path) = state.selected_namespace().map(str::to_string) {
+                        state.toggle_collapsed(&path, false);
+                    }
+                }
+                KeyCode::Up if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                    state.extend_marks(-1);
+                }
+                KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                    state.extend_marks(1);
+                }
+                KeyCode::Char(' ') => state.toggle_mark(),
+                KeyCode::Char('c') | KeyCode::Char('C') => {
+                    app.copy_source_to_target()?;
+                }
+                KeyCode::Char('x') | KeyCode::Char('X') => {
+                    app.clear_translation()?;
+                }
+                KeyCode::Char('w') | KeyCode::Char('W') => {
+                    app.cycle_status()?;
+                }
+                KeyCode::Char('e') | KeyCode::Char('E') => {
+                    app.export_xliff()?;
+                }
+                KeyCode::Char('i') | KeyCode::Char('I') => {
+                    app.import_xliff()?;
+                }
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    app.apply_memory_match();
+                }
+                KeyCode::Char('k') | KeyCode::Char('K') => {
+                    app.export_spreadsheet()?;
+                }
+                KeyCode::Char('l') | KeyCode::Char('L') => {
+                    app.import_spreadsheet()?;
+                }
+                KeyCode::Char('p') | KeyCode::Char('P') => {
+                    app.machine_translate_selected();
+                }
+                KeyCode::Char('d') | KeyCode::Char('D') => {
+                    app.machine_translate_pending()?;
+                }
+                KeyCode::Char('h') | KeyCode::Char('H') => {
+                    app.run_hook();
+                }
+                KeyCode::Char('j') | KeyCode::Char('J') => {
+                    app.add_to_dictionary();
+                }
+                KeyCode::Char('z') | KeyCode::Char('Z') => {
+                    app.toggle_lock()?;
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') => {
+                    if let Some(selected) = state.selected_index()
+                        && state.entries[selected].is_locked()
+                    {
+                        state.notify(app.locale.get("entry_locked").to_string());
+                    } else if let Some(selected) = state.selected_index()
+                        && let Value::String(original) = &state.entries[selected].original
+                    {
+                        state.input = validation::skeleton(original);
+                        state.cursor_pos = state.input.chars().count();
+                        state.editing = Some(selected);
+                    }
+                }
+                KeyCode::Esc if !state.marked.is_empty() => state.marked.clear(),
+                KeyCode::Up if !state.rows.is_empty() => {
                     let selected = state.table_state.selected().unwrap_or(0);
                     let new_selected = selected.saturating_sub(1);
                     state.table_state.select(Some(new_selected));
@@ -273,15 +325,56 @@ fn handle_editing(app: &mut App, key: KeyEvent) -> Result<()> {
                 KeyCode::Down => {
                     let selected = state.table_state.selected().unwrap_or(0);
                     let new_selected = selected + 1;
-                    if new_selected < state.entries.len() {
+                    if new_selected < state.rows.len() {
                         state.table_state.select(Some(new_selected));
                     }
                 }
+                KeyCode::PageUp => state.move_selection(-(state.page_size.max(1) as isize)),
+                KeyCode::PageDown => state.move_selection(state.page_size.max(1) as isize),
+                KeyCode::Home if !state.rows.is_empty() => state.table_state.select(Some(0)),
+                KeyCode::End if !state.rows.is_empty() => {
+                    state.table_state.select(Some(state.rows.len() - 1));
+                }
+                KeyCode::Tab => {
+                    state.jump_to(true, Entry::is_pending);
+                }
+                KeyCode::BackTab => {
+                    state.jump_to(false, Entry::is_pending);
+                }
+                KeyCode::Char(']') => {
+                    let glossary = Rc::clone(&state.glossary);
+                    state.jump_to(true, |entry| validation::has_issues(entry, &glossary));
+                }
+                KeyCode::Char('[') => {
+                    let glossary = Rc::clone(&state.glossary);
+                    state.jump_to(false, |entry| validation::has_issues(entry, &glossary));
+                }
+                KeyCode::Char('>') => {
+                    state.jump_to(true, |e| e.dirty);
+                }
+                KeyCode::Char('<') => {
+                    state.jump_to(false, |e| e.dirty);
+                }
+                KeyCode::Char('g') | KeyCode::Char('G') => {
+                    state.goto_mode = true;
+                    state.goto_input.clear();
+                }
+                KeyCode::Char('o') | KeyCode::Char('O') => {
+                    app.cycle_sort();
+                }
+                KeyCode::Char('a') | KeyCode::Char('A') => {
+                    state.auto_advance = !state.auto_advance;
+                }
                 KeyCode::Enter => {
-                    if let Some(selected) = state.table_state.selected() {
-                        state.editing = Some(selected);
-                        state.input = format_json_value(&state.entries[selected].translated);
-                        state.cursor_pos = state.input.chars().count();
+                    if let Some(path) = state.selected_namespace().map(str::to_string) {
+                        let collapse = !state.collapsed.contains(&path);
+                        state.toggle_collapsed(&path, collapse);
+                    } else if let Some(selected) = state.selected_index()
+                        && state.entries[selected].is_locked()
+                    {
+                        state.notify(app.locale.get("entry_locked").to_string());
+                    } else if let Some(selected) = state.selected_index() {
+                        state.start_editing(selected);
                     }
                 }
                 KeyCode::F(2) => {
@@ -301,6 +394,176 @@ fn handle_editing(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+fn handle_review(app: &mut App, key: KeyEvent) -> Result<()> {
+    let Some(state) = app.editing.as_mut() else {
+        return Ok(());
+    };
+    let Some(review) = state.review.as_mut() else {
+        return Ok(());
+    };
+    match key.code {
+        KeyCode::Up => review.selection = review.selection.saturating_sub(1),
+        KeyCode::Down if review.selection + 1 < review.changes.len() => {
+            review.selection += 1;
+        }
+        KeyCode::Char(' ') => {
+            if let Some(change) = review.changes.get_mut(review.selection) {
+                change.accepted = !change.accepted;
+            }
+        }
+        KeyCode::Char('a') | KeyCode::Char('A') => app.apply_review(true)?,
+        KeyCode::Enter => app.apply_review(false)?,
+        KeyCode::Esc => state.review = None,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_replace(app: &mut App, key: KeyEvent) -> Result<()> {
+    let Some(replace) = app.editing.as_mut().and_then(|s| s.replace.as_mut()) else {
+        return Ok(());
+    };
+
+    if replace.preview {
+        match key.code {
+            KeyCode::Up => replace.selection = replace.selection.saturating_sub(1),
+            KeyCode::Down if replace.selection + 1 < replace.matches.len() => {
+                replace.selection += 1;
+            }
+            KeyCode::Char(' ') => {
+                if let Some(found) = replace.matches.get_mut(replace.selection) {
+                    found.accepted = !found.accepted;
+                }
+            }
+            KeyCode::Char('a') | KeyCode::Char('A') => app.apply_replace(true),
+            KeyCode::Enter => app.apply_replace(false),
+            KeyCode::Esc => replace.preview = false,
+            _ => {}
+        }
+    } else {
+        match key.code {
+            KeyCode::Tab => {
+                replace.field = match replace.field {
+                    ReplaceField::Find => ReplaceField::Replace,
+                    ReplaceField::Replace => ReplaceField::Find,
+                };
+            }
+            KeyCode::Char(c) => replace.active_input().push(c),
+            KeyCode::Backspace => {
+                replace.active_input().pop();
+            }
+            KeyCode::Enter if !replace.find.is_empty() => app.update_replace_preview(),
+            KeyCode::Esc => {
+                if let Some(state) = &mut app.editing {
+                    state.replace = None;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn handle_goto(app: &mut App, key: KeyEvent) -> Result<()> {
+    let Some(state) = &mut app.editing else {
+        return Ok(());
+    };
+
+    match key.code {
+        KeyCode::Char(c) => state.goto_input.push(c),
+        KeyCode::Backspace => {
+            state.goto_input.pop();
+        }
+        KeyCode::Enter => {
+            let target = std::mem::take(&mut state.goto_input);
+            state.go_to(&target);
+            state.goto_mode = false;
+        }
+        KeyCode::Esc => {
+            state.goto_input.clear();
+            state.goto_mode = false;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_filter(app: &mut App, key: KeyEvent) -> Result<()> {
+    let Some(state) = &mut app.editing else {
+        return Ok(());
+    };
+
+    if state.filter_prefix_input {
+        match key.code {
+            KeyCode::Char(c) => {
+                state.filter.prefix.push(c);
+                state.refresh_view();
+            }
+            KeyCode::Backspace => {
+                state.filter.prefix.pop();
+                state.refresh_view();
+            }
+            KeyCode::Enter | KeyCode::Esc => state.filter_prefix_input = false,
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    match key.code {
+        KeyCode::Char(c @ '1'..='6') => app.toggle_filter(c),
+        KeyCode::Char('7') | KeyCode::Char('p') | KeyCode::Char('P') => {
+            state.filter_prefix_input = true;
+        }
+        KeyCode::Char('0') | KeyCode::Char('c') | KeyCode::Char('C') => {
+            state.filter = Filter::default();
+            state.refresh_view();
+        }
+        KeyCode::Enter | KeyCode::Esc | KeyCode::Char('f') | KeyCode::Char('F') => {
+            state.filter_mode = false;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_memory(app: &mut App, key: KeyEvent) -> Result<()> {
+    // Com uma remoção pendente, só `Y`/Enter confirmam; qualquer outra tecla cancela
+    if app.memory_screen.confirm.is_some() {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => app.confirm_purge()?,
+            _ => {
+                app.memory_screen.confirm = None;
+                app.memory_screen.message = None;
+            }
+        }
+        return Ok(());
+    }
+
+    let screen = &mut app.memory_screen;
+    match key.code {
+        KeyCode::Up => {
+            let selected = screen.list_state.selected().unwrap_or(0);
+            screen.list_state.select(Some(selected.saturating_sub(1)));
+        }
+        KeyCode::Down => {
+            let selected = screen.list_state.selected().unwrap_or(0);
+            // A primeira linha é a de todos os pares
+            if selected < screen.pairs.len() {
+                screen.list_state.select(Some(selected + 1));
+            }
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') => app.export_tmx(),
+        KeyCode::Char('i') | KeyCode::Char('I') => app.import_tmx(),
+        KeyCode::Char('d') | KeyCode::Char('D') => app.request_purge(false),
+        KeyCode::Char('o') | KeyCode::Char('O') => app.request_purge(true),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => {
+            app.state = AppState::FileSelection
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 fn handle_save_confirmation(app: &mut App, key: KeyEvent) -> Result<()> {
     let should_exit;
     let return_to;
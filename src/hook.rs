@@ -0,0 +1,207 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::app::{Entry, EntryStatus};
+use crate::config::Config;
+use crate::formats::{spreadsheet, text_of};
+
+// Intervalo entre as verificações de término do comando
+const POLL: Duration = Duration::from_millis(20);
+// Linhas finais do stderr incluídas na mensagem de erro
+const STDERR_LINES: usize = 5;
+
+// Dados enviados ao comando pelo stdin
+#[derive(Serialize)]
+struct HookInput<'a> {
+    file: String,
+    source_language: &'a str,
+    target_language: &'a str,
+    entries: Vec<HookEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct HookEntry<'a> {
+    key: &'a str,
+    original: String,
+    translation: String,
+    status: &'static str,
+}
+
+// Resposta no stdout: uma lista de sugestões, solta ou em `{"suggestions": [...]}`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HookOutput {
+    List(Vec<Suggestion>),
+    Object { suggestions: Vec<Suggestion> },
+}
+
+// Sugestão devolvida pelo comando; campos ausentes mantêm o valor atual
+#[derive(Deserialize)]
+pub struct Suggestion {
+    pub key: String,
+    pub translation: Option<String>,
+    pub status: Option<String>,
+}
+
+impl Suggestion {
+    pub fn status(&self) -> Option<EntryStatus> {
+        self.status.as_deref().and_then(spreadsheet::parse_status)
+    }
+}
+
+// Executa o comando de `hook_command` com as entradas em JSON no stdin e lê as
+// sugestões do stdout. Estouro do tempo limite, código de saída diferente de
+// zero e saída inválida viram erros com o stderr do comando.
+pub fn run<'a>(
+    config: &Config,
+    file: &Path,
+    entries: impl Iterator<Item = &'a Entry>,
+) -> Result<Vec<Suggestion>> {
+    let command = config
+        .hook_command
+        .as_deref()
+        .context("no external command configured (hook_command)")?;
+    let input = HookInput {
+        file: file.display().to_string(),
+        source_language: &config.source_language,
+        target_language: &config.target_language,
+        entries: entries
+            .map(|entry| HookEntry {
+                key: &entry.key,
+                original: text_of(&entry.original),
+                translation: text_of(&entry.translated),
+                status: spreadsheet::status_name(entry.status),
+            })
+            .collect(),
+    };
+    let input = serde_json::to_vec(&input)?;
+
+    let mut child = shell(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to start `{}`", command))?;
+
+    // Escrita e leituras em threads próprias, para o comando não travar com
+    // os pipes cheios. O comando pode terminar sem ler a entrada; isso não é
+    // um erro, e a escrita não é aguardada.
+    let mut stdin = child.stdin.take().context("stdin")?;
+    thread::spawn(move || stdin.write_all(&input));
+    let mut stdout = child.stdout.take().context("stdout")?;
+    let (output_sender, output_receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        let _ = output_sender.send(stdout.read_to_end(&mut output).map(|_| output));
+    });
+    let mut stderr = child.stderr.take().context("stderr")?;
+    let (error_sender, error_receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stderr.read_to_string(&mut output);
+        let _ = error_sender.send(output);
+    });
+
+    // O limite vale para o comando e para a leitura dos pipes, que processos
+    // em segundo plano podem manter abertos depois que o comando termina
+    let deadline = Instant::now() + Duration::from_secs(config.hook_timeout);
+    let remaining = || deadline.saturating_duration_since(Instant::now());
+    let timed_out = |child: &mut Child| {
+        kill(child);
+        anyhow::anyhow!("`{}` timed out after {}s", command, config.hook_timeout)
+    };
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if remaining().is_zero() {
+            return Err(timed_out(&mut child));
+        }
+        thread::sleep(POLL);
+    };
+
+    let Ok(stderr) = error_receiver.recv_timeout(remaining()) else {
+        return Err(timed_out(&mut child));
+    };
+    if !status.success() {
+        let tail: Vec<&str> = stderr.lines().rev().take(STDERR_LINES).collect();
+        let tail: Vec<&str> = tail.into_iter().rev().collect();
+        match status.code() {
+            Some(code) => bail!(
+                "`{}` exited with code {}: {}",
+                command,
+                code,
+                tail.join(" / ")
+            ),
+            None => bail!("`{}` was terminated: {}", command, tail.join(" / ")),
+        }
+    }
+    let Ok(output) = output_receiver.recv_timeout(remaining()) else {
+        return Err(timed_out(&mut child));
+    };
+    let output = output.context("failed to read the output")?;
+
+    let output: HookOutput = serde_json::from_slice(&output)
+        .with_context(|| format!("invalid output from `{}`", command))?;
+    Ok(match output {
+        HookOutput::List(suggestions) | HookOutput::Object { suggestions } => suggestions,
+    })
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.args(["/C", command]);
+    shell
+}
+
+// O comando fica num grupo de processos próprio, para que o tempo limite
+// encerre também o que ele iniciou em segundo plano
+#[cfg(not(windows))]
+fn shell(command: &str) -> Command {
+    use std::os::unix::process::CommandExt;
+
+    let mut shell = Command::new("sh");
+    shell.args(["-c", command]).process_group(0);
+    shell
+}
+
+#[cfg(windows)]
+fn kill(child: &mut Child) {
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(not(windows))]
+fn kill(child: &mut Child) {
+    // O grupo tem o PID do próprio `sh`
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    let _ = child.wait();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(windows))]
+    #[test]
+    fn background_process_holding_the_pipes_times_out() {
+        let config = Config {
+            hook_command: Some("sleep 30 & echo x".to_string()),
+            hook_timeout: 1,
+            ..Config::default()
+        };
+        let started = Instant::now();
+        let result = run(&config, Path::new("messages.json"), std::iter::empty());
+        assert!(result.is_err_and(|error| error.to_string().contains("timed out")));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
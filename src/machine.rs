@@ -0,0 +1,110 @@
+use anyhow::{Context, Result, bail};
+use serde_json::{Value, json};
+use std::time::Duration;
+
+use crate::config::Config;
+
+// Textos enviados por requisição na tradução em lote
+const BATCH: usize = 50;
+
+// Serviço de tradução automática. Recebe vários textos de uma vez e devolve
+// as traduções na mesma ordem.
+pub trait Provider {
+    fn translate(&self, texts: &[String], source: &str, target: &str) -> Result<Vec<String>>;
+}
+
+// Cliente da API do LibreTranslate (`POST /translate`), que também atende
+// servidores locais e compatíveis
+pub struct LibreTranslate {
+    url: String,
+    api_key: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl LibreTranslate {
+    pub fn new(url: &str, api_key: Option<String>, timeout: Duration) -> Self {
+        Self {
+            url: format!("{}/translate", url.trim_end_matches('/')),
+            api_key,
+            agent: ureq::AgentBuilder::new().timeout(timeout).build(),
+        }
+    }
+}
+
+impl Provider for LibreTranslate {
+    fn translate(&self, texts: &[String], source: &str, target: &str) -> Result<Vec<String>> {
+        let mut body = json!({
+            "q": texts,
+            "source": source,
+            "target": target,
+            "format": "text",
+        });
+        if let Some(key) = &self.api_key {
+            body["api_key"] = Value::String(key.clone());
+        }
+
+        let response: Value = match self.agent.post(&self.url).send_json(body) {
+            Ok(response) => response.into_json()?,
+            // O servidor explica o erro no campo `error`
+            Err(ureq::Error::Status(code, response)) => {
+                let message = response
+                    .into_json::<Value>()
+                    .ok()
+                    .and_then(|body| body["error"].as_str().map(str::to_string))
+                    .unwrap_or_default();
+                bail!("HTTP {} {}", code, message);
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        let translated: Vec<String> = match &response["translatedText"] {
+            Value::Array(items) => items
+                .iter()
+                .map(|item| item.as_str().unwrap_or_default().to_string())
+                .collect(),
+            Value::String(text) => vec![text.clone()],
+            _ => bail!("unexpected response from {}", self.url),
+        };
+        if translated.len() != texts.len() {
+            bail!(
+                "expected {} translations, received {}",
+                texts.len(),
+                translated.len()
+            );
+        }
+        Ok(translated)
+    }
+}
+
+// Provedor escolhido em `mt_provider`
+pub fn provider(config: &Config) -> Result<Box<dyn Provider>> {
+    let url = config
+        .mt_url
+        .as_deref()
+        .context("machine translation is not configured (mt_url)")?;
+    match config.mt_provider.as_str() {
+        "libretranslate" => Ok(Box::new(LibreTranslate::new(
+            url,
+            config.mt_api_key.clone(),
+            Duration::from_secs(config.mt_timeout),
+        ))),
+        other => bail!("unknown machine translation provider: {}", other),
+    }
+}
+
+// Traduz os textos em requisições de até `BATCH` itens
+pub fn translate_all(
+    provider: &dyn Provider,
+    texts: &[String],
+    config: &Config,
+) -> Result<Vec<String>> {
+    let mut translated = Vec::with_capacity(texts.len());
+    for chunk in texts.chunks(BATCH) {
+        translated.extend(provider.translate(
+            chunk,
+            &config.source_language,
+            &config.target_language,
+        )?);
+    }
+    Ok(translated)
+}
@@ -1,8 +1,19 @@
 mod app;
+mod config;
 mod file_operations;
+mod find_replace;
+mod formats;
+mod glossary;
 mod handlers;
+mod hook;
 mod localization;
+mod machine;
+mod memory;
+mod review;
+mod spelling;
+mod tree;
 mod ui;
+mod validation;
 
 use anyhow::Result;
 use crossterm::{
@@ -17,6 +28,9 @@ use app::{App, AppState};
 use handlers::handle_events;
 
 fn main() -> Result<()> {
+    // Initiate app before touching the terminal, so startup errors are printed normally
+    let mut app = App::new()?;
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -24,9 +38,6 @@ fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Initiate app
-    let mut app = App::new()?;
-
     // Run app
     let result = run_app(&mut terminal, &mut app);
 
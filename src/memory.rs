@@ -0,0 +1,516 @@
+use anyhow::{Context, Result, bail};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::app::{Entry, EntryStatus};
+use crate::config::Config;
+
+const MEMORY_FILE: &str = "memory.jsonl";
+// Candidatos mais parecidos pelos trigramas que passam para o cálculo da distância
+const CANDIDATES: usize = 50;
+
+// Par origem/tradução guardado na memória, com o arquivo e a chave de onde veio
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Segment {
+    pub source: String,
+    pub target: String,
+    pub source_language: String,
+    pub target_language: String,
+    #[serde(default)]
+    pub file: String,
+    #[serde(default)]
+    pub key: String,
+    // Data de criação em RFC 3339
+    pub created: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, String>,
+}
+
+// Segmentos a remover na tela de gerenciamento da memória
+pub enum Purge {
+    All,
+    Pair(String, String),
+    // Segmentos de arquivos que não existem mais
+    Orphaned,
+}
+
+impl Purge {
+    fn matches(&self, segment: &Segment) -> bool {
+        match self {
+            Purge::All => true,
+            Purge::Pair(source, target) => {
+                segment.source_language == *source && segment.target_language == *target
+            }
+            Purge::Orphaned => !segment.file.is_empty() && !Path::new(&segment.file).exists(),
+        }
+    }
+}
+
+pub struct MemoryMatch {
+    // Semelhança com o texto procurado, em porcentagem
+    pub score: u8,
+    pub source: String,
+    pub target: String,
+    pub file: String,
+}
+
+// Memória de tradução local, compartilhada entre arquivos e projetos. Fica num
+// arquivo JSON Lines, e cada gravação só acrescenta os segmentos novos.
+pub struct TranslationMemory {
+    path: PathBuf,
+    segments: Vec<Segment>,
+    // Trigramas do texto de origem → segmentos que os contêm
+    index: HashMap<u64, Vec<u32>>,
+    // Quantidade de trigramas distintos da origem de cada segmento
+    grams: Vec<u32>,
+    known: HashSet<u64>,
+    // Falso quando o arquivo não pôde ser lido: regravá-lo perderia o conteúdo
+    readable: bool,
+}
+
+impl TranslationMemory {
+    fn empty(config: &Config) -> Self {
+        let path = match &config.memory_path {
+            Some(path) => PathBuf::from(path),
+            None => dirs::data_dir()
+                .map(|dir| dir.join("transtui"))
+                .unwrap_or_default()
+                .join(MEMORY_FILE),
+        };
+        Self {
+            path,
+            segments: Vec::new(),
+            index: HashMap::new(),
+            grams: Vec::new(),
+            known: HashSet::new(),
+            readable: true,
+        }
+    }
+
+    // Memória vazia para quando o arquivo está inválido. Novos segmentos ainda
+    // são acrescentados ao arquivo, mas a remoção fica bloqueada.
+    pub fn disabled(config: &Config) -> Self {
+        Self {
+            readable: false,
+            ..Self::empty(config)
+        }
+    }
+
+    pub fn load(config: &Config) -> Result<Self> {
+        let mut memory = Self::empty(config);
+        if memory.path.exists() {
+            let content = fs::read_to_string(&memory.path)?;
+            for (number, line) in content.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let segment = serde_json::from_str(line)
+                    .with_context(|| format!("{}:{}", memory.path.display(), number + 1))?;
+                memory.insert(segment);
+            }
+        }
+        Ok(memory)
+    }
+
+    // Guarda o segmento se ele ainda não existir. Retorna se foi incluído.
+    fn insert(&mut self, mut segment: Segment) -> bool {
+        segment.source_language = language_tag(&segment.source_language);
+        segment.target_language = language_tag(&segment.target_language);
+        if !self.known.insert(identity(&segment)) {
+            return false;
+        }
+        let id = self.segments.len() as u32;
+        let grams = trigrams(&segment.source);
+        for &gram in &grams {
+            self.index.entry(gram).or_default().push(id);
+        }
+        self.grams.push(grams.len() as u32);
+        self.segments.push(segment);
+        true
+    }
+
+    // Acrescenta as entradas traduzidas de um arquivo salvo. Retorna quantos
+    // segmentos novos foram gravados.
+    pub fn add_entries(
+        &mut self,
+        entries: &[Entry],
+        file: &Path,
+        config: &Config,
+    ) -> Result<usize> {
+        let file = fs::canonicalize(file)
+            .unwrap_or_else(|_| file.to_path_buf())
+            .display()
+            .to_string();
+        let created = Local::now().to_rfc3339();
+
+        let mut added = Vec::new();
+        for entry in entries {
+            let (Value::String(source), Value::String(target)) =
+                (&entry.original, &entry.translated)
+            else {
+                continue;
+            };
+            // Rascunhos da tradução automática e traduções a revisar não viram
+            // memória confiável
+            if entry.is_locked()
+                || matches!(entry.status, EntryStatus::Draft | EntryStatus::NeedsReview)
+                || source.trim().is_empty()
+                || target.trim().is_empty()
+                || (entry.status == EntryStatus::Untranslated && source == target)
+            {
+                continue;
+            }
+            let segment = Segment {
+                source: source.clone(),
+                target: target.clone(),
+                source_language: config.source_language.clone(),
+                target_language: config.target_language.clone(),
+                file: file.clone(),
+                key: entry.key.clone(),
+                created: created.clone(),
+                attributes: BTreeMap::new(),
+            };
+            if self.insert(segment) {
+                added.push(self.segments.len() - 1);
+            }
+        }
+        self.append(&added)?;
+        Ok(added.len())
+    }
+
+    fn append(&self, ids: &[usize]) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let mut lines = String::new();
+        for &id in ids {
+            lines.push_str(&serde_json::to_string(&self.segments[id])?);
+            lines.push('\n');
+        }
+        file.write_all(lines.as_bytes())?;
+        Ok(())
+    }
+
+    // Acrescenta segmentos importados, ignorando os repetidos. Retorna quantos
+    // foram incluídos e quantos já existiam.
+    pub fn import(&mut self, segments: Vec<Segment>) -> Result<(usize, usize)> {
+        let total = segments.len();
+        let mut added = Vec::new();
+        for segment in segments {
+            if self.insert(segment) {
+                added.push(self.segments.len() - 1);
+            }
+        }
+        self.append(&added)?;
+        Ok((added.len(), total - added.len()))
+    }
+
+    // Quantidade de segmentos por par de idiomas (origem, destino)
+    pub fn pairs(&self) -> Vec<(String, String, usize)> {
+        let mut counts: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+        for segment in &self.segments {
+            *counts
+                .entry((&segment.source_language, &segment.target_language))
+                .or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .map(|((source, target), count)| (source.to_string(), target.to_string(), count))
+            .collect()
+    }
+
+    pub fn count(&self, purge: &Purge) -> usize {
+        self.segments
+            .iter()
+            .filter(|segment| purge.matches(segment))
+            .count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    // Segmentos do par de idiomas, ou todos quando `pair` é `None`
+    pub fn segments(&self, pair: Option<(&str, &str)>) -> Vec<&Segment> {
+        self.segments
+            .iter()
+            .filter(|segment| {
+                pair.is_none_or(|(source, target)| {
+                    segment.source_language == source && segment.target_language == target
+                })
+            })
+            .collect()
+    }
+
+    // Remove segmentos e regrava o arquivo. Retorna quantos foram removidos.
+    pub fn purge(&mut self, purge: &Purge) -> Result<usize> {
+        if !self.readable {
+            bail!(
+                "translation memory {} could not be read; fix it before removing segments",
+                self.path.display()
+            );
+        }
+        let before = self.segments.len();
+        let segments = std::mem::take(&mut self.segments);
+        self.index.clear();
+        self.grams.clear();
+        self.known.clear();
+        for segment in segments {
+            if !purge.matches(&segment) {
+                self.insert(segment);
+            }
+        }
+
+        let mut content = String::new();
+        for segment in &self.segments {
+            content.push_str(&serde_json::to_string(segment)?);
+            content.push('\n');
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, content)?;
+        Ok(before - self.segments.len())
+    }
+
+    // Melhores correspondências aproximadas para o texto, no mesmo par de
+    // idiomas. O índice de trigramas limita o cálculo da distância de edição a
+    // poucos candidatos, mesmo com centenas de milhares de segmentos.
+    pub fn lookup(
+        &self,
+        text: &str,
+        config: &Config,
+        exclude: (&str, &str),
+        limit: usize,
+    ) -> Vec<MemoryMatch> {
+        if text.trim().is_empty() {
+            return Vec::new();
+        }
+        let grams = trigrams(text);
+        let mut shared = vec![0u32; self.segments.len()];
+        for gram in &grams {
+            for &id in self.index.get(gram).into_iter().flatten() {
+                shared[id as usize] += 1;
+            }
+        }
+        // Coeficiente de Dice sobre os conjuntos de trigramas: a contagem crua
+        // favoreceria segmentos longos que contêm o texto em vez da correspondência
+        // exata, que assim fica sempre entre os primeiros
+        let mut candidates: Vec<(usize, f64)> = shared
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, count)| count > 0)
+            .map(|(id, count)| {
+                let total = grams.len() as u32 + self.grams[id];
+                (id, 2.0 * count as f64 / total as f64)
+            })
+            .collect();
+        candidates.sort_unstable_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let length = text.chars().count();
+        let mut matches: Vec<MemoryMatch> = Vec::new();
+        for segment in candidates
+            .into_iter()
+            .map(|(id, _)| &self.segments[id])
+            .filter(|segment| {
+                same_language(&segment.source_language, &config.source_language)
+                    && same_language(&segment.target_language, &config.target_language)
+                    && !(segment.file == exclude.0 && segment.key == exclude.1)
+            })
+            .take(CANDIDATES)
+        {
+            let longest = length.max(segment.source.chars().count());
+            let distance = strsim::levenshtein(text, &segment.source);
+            let score = (100 * longest.saturating_sub(distance) / longest) as u8;
+            if score < config.memory_threshold
+                || matches.iter().any(|found| found.target == segment.target)
+            {
+                continue;
+            }
+            matches.push(MemoryMatch {
+                score,
+                source: segment.source.clone(),
+                target: segment.target.clone(),
+                file: segment.file.clone(),
+            });
+        }
+        matches.sort_by_key(|found| Reverse(found.score));
+        matches.truncate(limit);
+        matches
+    }
+}
+
+// `EN_us` vira `en-US`: idioma em minúsculas, região em maiúsculas e escrita
+// com a inicial maiúscula (`zh-Hant`)
+fn language_tag(code: &str) -> String {
+    code.split(['-', '_'])
+        .enumerate()
+        .map(|(i, part)| match (i, part.len()) {
+            (0, _) => part.to_lowercase(),
+            (_, 2) => part.to_uppercase(),
+            (_, 4) if part.is_ascii() => part[..1].to_uppercase() + &part[1..].to_lowercase(),
+            _ => part.to_lowercase(),
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+// Marcas iguais, ou uma só com o idioma da outra (`en` e `en-US`); regiões
+// diferentes (`pt-BR` e `pt-PT`) não casam
+fn same_language(a: &str, b: &str) -> bool {
+    let (a, b) = (language_tag(a), language_tag(b));
+    let base = |tag: &str| tag.split('-').next().unwrap_or_default().to_string();
+    a == b || (base(&a) == base(&b) && (!a.contains('-') || !b.contains('-')))
+}
+
+// Origem, tradução e par de idiomas identificam um segmento repetido
+fn identity(segment: &Segment) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (
+        &segment.source,
+        &segment.target,
+        &segment.source_language,
+        &segment.target_language,
+    )
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+// Trigramas do texto em minúsculas, cada um com os três caracteres num `u64`
+fn trigrams(text: &str) -> HashSet<u64> {
+    let chars: Vec<char> = format!(" {} ", text.to_lowercase()).chars().collect();
+    chars
+        .windows(3)
+        .map(|w| ((w[0] as u64) << 42) | ((w[1] as u64) << 21) | w[2] as u64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::Lock;
+    use crate::formats::{self, tmx};
+
+    fn segment(source: &str, target: &str) -> Segment {
+        Segment {
+            source: source.to_string(),
+            target: target.to_string(),
+            source_language: "en".to_string(),
+            target_language: "pt".to_string(),
+            file: String::new(),
+            key: String::new(),
+            created: String::new(),
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn exact_match_survives_longer_segments_containing_the_text() {
+        let config = Config {
+            memory_path: Some("/nonexistent/memory.jsonl".to_string()),
+            ..Config::default()
+        };
+        let mut memory = TranslationMemory::load(&config).unwrap();
+        for i in 0..200 {
+            memory.insert(segment(
+                &format!("Save the current document before closing window {}", i),
+                &format!("Salvar o documento {}", i),
+            ));
+        }
+        memory.insert(segment("Save", "Salvar"));
+
+        let matches = memory.lookup("Save", &config, ("other.json", "other"), 3);
+        assert_eq!(matches[0].target, "Salvar");
+        assert_eq!(matches[0].score, 100);
+    }
+
+    #[test]
+    fn utf16_tmx_matches_the_configured_languages() {
+        let dir = std::env::temp_dir().join("transtui-memory-tmx");
+        let _ = fs::remove_dir_all(&dir);
+        let config = Config {
+            memory_path: Some(dir.join("memory.jsonl").to_string_lossy().to_string()),
+            source_language: "en".to_string(),
+            target_language: "pt-BR".to_string(),
+            ..Config::default()
+        };
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tmx/utf16.tmx");
+        let segments = tmx::import(&formats::read(&fixture).unwrap()).unwrap();
+
+        let mut memory = TranslationMemory::load(&config).unwrap();
+        assert_eq!(memory.import(segments).unwrap(), (1, 0));
+        assert_eq!(
+            memory.pairs(),
+            [("en-US".to_string(), "pt-BR".to_string(), 1)]
+        );
+        let matches = memory.lookup("Save changes", &config, ("other.json", "other"), 3);
+        assert_eq!(matches[0].target, "Salvar alterações");
+    }
+
+    #[test]
+    fn drafts_and_entries_under_review_are_not_stored() {
+        let dir = std::env::temp_dir().join("transtui-memory-drafts");
+        let _ = fs::remove_dir_all(&dir);
+        let config = Config {
+            memory_path: Some(dir.join("memory.jsonl").to_string_lossy().to_string()),
+            ..Config::default()
+        };
+        let entry = |key: &str, target: &str, status| Entry {
+            key: key.to_string(),
+            original: Value::String(format!("Source {}", key)),
+            translated: Value::String(target.to_string()),
+            status,
+            lock: Lock::Unlocked,
+            max_length: None,
+            dirty: true,
+            modified: None,
+            notes: Vec::new(),
+        };
+        let entries = [
+            entry("draft", "Rascunho", EntryStatus::Draft),
+            entry("review", "Revisar", EntryStatus::NeedsReview),
+            entry("done", "Pronto", EntryStatus::Translated),
+        ];
+
+        let mut memory = TranslationMemory::load(&config).unwrap();
+        assert_eq!(
+            memory
+                .add_entries(&entries, Path::new("messages.json"), &config)
+                .unwrap(),
+            1
+        );
+        assert_eq!(memory.segments(None)[0].target, "Pronto");
+    }
+
+    #[test]
+    fn invalid_memory_file_is_not_rewritten() {
+        let dir = std::env::temp_dir().join("transtui-memory-invalid");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("memory.jsonl");
+        fs::write(&path, "{not json}\n").unwrap();
+        let config = Config {
+            memory_path: Some(path.to_string_lossy().to_string()),
+            ..Config::default()
+        };
+
+        assert!(TranslationMemory::load(&config).is_err());
+        let mut memory = TranslationMemory::disabled(&config);
+        assert!(memory.purge(&Purge::All).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{not json}\n");
+    }
+}
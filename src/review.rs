@@ -0,0 +1,64 @@
+use serde_json::Value;
+
+use crate::app::{Entry, EntryStatus};
+
+// Alteração proposta por uma fonte externa (planilha importada, por exemplo),
+// exibida para revisão antes de ser aplicada à entrada
+pub struct ProposedChange {
+    pub entry_index: usize,
+    pub translated: Option<Value>,
+    pub status: Option<EntryStatus>,
+    pub accepted: bool,
+}
+
+pub struct ReviewState {
+    pub changes: Vec<ProposedChange>,
+    pub selection: usize,
+    // Chaves recebidas que não existem no arquivo aberto
+    pub unmatched: Vec<String>,
+}
+
+impl ReviewState {
+    pub fn new(changes: Vec<ProposedChange>, unmatched: Vec<String>) -> Self {
+        Self {
+            changes,
+            selection: 0,
+            unmatched,
+        }
+    }
+}
+
+// Converte o texto recebido para o tipo do valor original: números e listas
+// voltam a ser JSON em vez de texto
+pub fn typed_value(original: &Value, text: String) -> Value {
+    match original {
+        Value::String(_) => Value::String(text),
+        _ => serde_json::from_str(&text).unwrap_or(Value::String(text)),
+    }
+}
+
+// Monta a alteração proposta para a entrada, mantendo só o que difere do
+// estado atual. Retorna `None` quando nada mudaria.
+pub fn propose(
+    entry: &Entry,
+    entry_index: usize,
+    translated: Option<String>,
+    status: Option<EntryStatus>,
+) -> Option<ProposedChange> {
+    if entry.is_locked() {
+        return None;
+    }
+    let translated = translated
+        .map(|text| typed_value(&entry.original, text))
+        .filter(|value| *value != entry.translated);
+    let status = status.filter(|status| *status != entry.status);
+    if translated.is_none() && status.is_none() {
+        return None;
+    }
+    Some(ProposedChange {
+        entry_index,
+        translated,
+        status,
+        accepted: true,
+    })
+}
@@ -0,0 +1,590 @@
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::config::Config;
+use crate::validation;
+
+// Palavras do texto, incluindo apóstrofos e hífens internos (`d'água`, `guarda-chuva`)
+static WORD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[\p{L}\p{M}]+(?:['’-][\p{L}\p{M}]+)*").unwrap());
+
+// Sugestões mostradas por palavra
+const SUGGESTIONS: usize = 5;
+
+#[derive(Default)]
+pub struct Speller {
+    dictionary: Option<Dictionary>,
+    personal: HashSet<String>,
+    personal_path: PathBuf,
+}
+
+impl Speller {
+    // Lê o dicionário Hunspell do idioma de destino em `spell_path` e o
+    // dicionário pessoal do projeto. Sem `spell_path`, a verificação fica desligada.
+    pub fn load(config: &Config) -> Result<Self> {
+        let personal_path = PathBuf::from(&config.spell_personal_path);
+        let Some(path) = &config.spell_path else {
+            return Ok(Self::disabled(config));
+        };
+        let (aff, dic) = dictionary_files(Path::new(path), &config.target_language)?;
+        let dictionary = Dictionary::load(&aff, &dic)
+            .with_context(|| format!("invalid Hunspell dictionary {}", dic.display()))?;
+
+        let personal = match fs::read_to_string(&personal_path) {
+            Ok(content) => content
+                .lines()
+                .map(|line| line.trim().to_lowercase())
+                .filter(|line| !line.is_empty())
+                .collect(),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => HashSet::new(),
+            Err(error) => {
+                return Err(error)
+                    .with_context(|| format!("personal dictionary {}", personal_path.display()));
+            }
+        };
+        Ok(Self {
+            dictionary: Some(dictionary),
+            personal,
+            personal_path,
+        })
+    }
+
+    // Sem dicionário: nada é marcado, mas `J` continua gravando no dicionário pessoal
+    pub fn disabled(config: &Config) -> Self {
+        Self {
+            personal_path: PathBuf::from(&config.spell_personal_path),
+            ..Self::default()
+        }
+    }
+
+    // Trechos do texto com palavras desconhecidas, ignorando placeholders,
+    // tags e palavras com dígitos
+    pub fn misspelled(&self, text: &str) -> Vec<Range<usize>> {
+        let Some(dictionary) = &self.dictionary else {
+            return Vec::new();
+        };
+        let markup: Vec<Range<usize>> = validation::PLACEHOLDER
+            .find_iter(text)
+            .chain(validation::TAG.find_iter(text))
+            .map(|m| m.range())
+            .collect();
+        WORD.find_iter(text)
+            .filter(|word| {
+                let range = word.range();
+                !markup
+                    .iter()
+                    .any(|m| m.start < range.end && range.start < m.end)
+                    && !text[range.end..].starts_with(|c: char| c.is_ascii_digit())
+                    && !text[..range.start].ends_with(|c: char| c.is_ascii_digit())
+            })
+            .filter(|word| {
+                let word = word.as_str();
+                !self.personal.contains(&word.to_lowercase())
+                    && !dictionary.check(word)
+                    && !word.split('-').all(|part| dictionary.check(part))
+            })
+            .map(|word| word.range())
+            .collect()
+    }
+
+    // Palavras desconhecidas do texto, sem repetição
+    pub fn misspelled_words(&self, text: &str) -> Vec<String> {
+        let mut words: Vec<String> = Vec::new();
+        for range in self.misspelled(text) {
+            let word = text[range].to_string();
+            if !words.contains(&word) {
+                words.push(word);
+            }
+        }
+        words
+    }
+
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        match &self.dictionary {
+            Some(dictionary) => dictionary.suggest(word),
+            None => Vec::new(),
+        }
+    }
+
+    // Acrescenta palavras ao dicionário pessoal do projeto
+    pub fn add_words(&mut self, words: &[String]) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.personal_path)
+            .with_context(|| format!("personal dictionary {}", self.personal_path.display()))?;
+        for word in words {
+            if self.personal.insert(word.to_lowercase()) {
+                writeln!(file, "{}", word)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Resultado da verificação de um texto, válido enquanto o texto não muda
+struct Checked {
+    text: String,
+    misspelled: Vec<Range<usize>>,
+    // Palavras desconhecidas com as sugestões, calculadas só quando pedidas
+    suggestions: Option<Vec<(String, Vec<String>)>>,
+}
+
+impl Checked {
+    fn new(text: &str, speller: &Speller) -> Self {
+        Self {
+            text: text.to_string(),
+            misspelled: speller.misspelled(text),
+            suggestions: None,
+        }
+    }
+}
+
+// Verificações já feitas por entrada e para a caixa de edição. A tela é
+// redesenhada a cada tecla; um texto só é verificado de novo quando muda.
+#[derive(Default)]
+pub struct SpellCache {
+    entries: HashMap<usize, Checked>,
+    input: Option<Checked>,
+}
+
+impl SpellCache {
+    fn checked(&mut self, index: usize, text: &str, speller: &Speller) -> &mut Checked {
+        let checked = self
+            .entries
+            .entry(index)
+            .or_insert_with(|| Checked::new(text, speller));
+        if checked.text != text {
+            *checked = Checked::new(text, speller);
+        }
+        checked
+    }
+
+    pub fn misspelled(&mut self, index: usize, text: &str, speller: &Speller) -> &[Range<usize>] {
+        &self.checked(index, text, speller).misspelled
+    }
+
+    // Palavras desconhecidas, sem repetição, com as sugestões de cada uma
+    pub fn suggestions(
+        &mut self,
+        index: usize,
+        text: &str,
+        speller: &Speller,
+    ) -> &[(String, Vec<String>)] {
+        let checked = self.checked(index, text, speller);
+        checked.suggestions.get_or_insert_with(|| {
+            let mut words: Vec<(String, Vec<String>)> = Vec::new();
+            for range in &checked.misspelled {
+                let word = &checked.text[range.clone()];
+                if !words.iter().any(|(known, _)| known == word) {
+                    words.push((word.to_string(), speller.suggest(word)));
+                }
+            }
+            words
+        })
+    }
+
+    pub fn input(&mut self, text: &str, speller: &Speller) -> &[Range<usize>] {
+        if self
+            .input
+            .as_ref()
+            .is_none_or(|checked| checked.text != text)
+        {
+            self.input = Some(Checked::new(text, speller));
+        }
+        &self.input.as_ref().unwrap().misspelled
+    }
+
+    // Depois de mudar o dicionário pessoal, tudo precisa ser verificado de novo
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.input = None;
+    }
+}
+
+// Arquivos `.aff`/`.dic`: `spell_path` pode apontar para um deles ou para a
+// pasta com os dicionários, procurados pelo idioma de destino (`pt-BR`,
+// `pt_BR` e `pt`)
+fn dictionary_files(path: &Path, language: &str) -> Result<(PathBuf, PathBuf)> {
+    if path
+        .extension()
+        .is_some_and(|ext| ext == "aff" || ext == "dic")
+    {
+        return Ok((path.with_extension("aff"), path.with_extension("dic")));
+    }
+    let underscored = language.replace('-', "_");
+    let base = language.split(['-', '_']).next().unwrap_or(language);
+    for name in [language, underscored.as_str(), base] {
+        let dic = path.join(format!("{}.dic", name));
+        if dic.exists() {
+            return Ok((dic.with_extension("aff"), dic));
+        }
+    }
+    bail!(
+        "no Hunspell dictionary for {} in {}",
+        language,
+        path.display()
+    )
+}
+
+type Flag = u32;
+
+#[derive(Clone, Copy, PartialEq)]
+enum FlagFormat {
+    Char,
+    Long,
+    Numeric,
+}
+
+// Condição de um afixo: uma classe por caractere (`.`, `a`, `[aeiou]`, `[^y]`)
+enum CharClass {
+    Any,
+    Set(Vec<char>, bool),
+}
+
+impl CharClass {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            CharClass::Any => true,
+            CharClass::Set(chars, negated) => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+struct Affix {
+    flag: Flag,
+    cross: bool,
+    strip: String,
+    add: String,
+    condition: Vec<CharClass>,
+}
+
+impl Affix {
+    // A condição vale para o início (prefixo) ou o fim (sufixo) da raiz
+    fn condition_matches(&self, root: &str, prefix: bool) -> bool {
+        let chars: Vec<char> = root.chars().collect();
+        if chars.len() < self.condition.len() {
+            return false;
+        }
+        let start = if prefix {
+            0
+        } else {
+            chars.len() - self.condition.len()
+        };
+        self.condition
+            .iter()
+            .zip(&chars[start..])
+            .all(|(class, &c)| class.matches(c))
+    }
+}
+
+// Subconjunto do formato Hunspell: palavras com flags, prefixos e sufixos
+// (inclusive combinados), NEEDAFFIX, FORBIDDENWORD, TRY e REP. Compostos e
+// afixos de dois níveis não são tratados.
+struct Dictionary {
+    words: HashMap<String, Vec<Flag>>,
+    prefixes: HashMap<String, Vec<Affix>>,
+    suffixes: HashMap<String, Vec<Affix>>,
+    need_affix: Option<Flag>,
+    forbidden: Option<Flag>,
+    try_chars: Vec<char>,
+    replacements: Vec<(String, String)>,
+}
+
+impl Dictionary {
+    fn load(aff: &Path, dic: &Path) -> Result<Self> {
+        let aff_bytes = fs::read(aff).with_context(|| format!("{}", aff.display()))?;
+        let dic_bytes = fs::read(dic).with_context(|| format!("{}", dic.display()))?;
+        // A codificação vem da linha `SET`; sem UTF-8, ISO-8859-*
+        let utf8 = latin1(&aff_bytes)
+            .lines()
+            .any(|line| line.trim().eq_ignore_ascii_case("SET UTF-8"));
+        let decode = |bytes: &[u8]| {
+            if utf8 {
+                String::from_utf8_lossy(bytes).into_owned()
+            } else {
+                latin1(bytes)
+            }
+        };
+
+        let mut dictionary = Self {
+            words: HashMap::new(),
+            prefixes: HashMap::new(),
+            suffixes: HashMap::new(),
+            need_affix: None,
+            forbidden: None,
+            try_chars: Vec::new(),
+            replacements: Vec::new(),
+        };
+        let mut format = FlagFormat::Char;
+        let mut cross = HashMap::new();
+        for line in decode(&aff_bytes).lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["FLAG", "long", ..] => format = FlagFormat::Long,
+                ["FLAG", "num", ..] => format = FlagFormat::Numeric,
+                ["NEEDAFFIX", flag, ..] => {
+                    dictionary.need_affix = parse_flags(flag, format).first().copied()
+                }
+                ["FORBIDDENWORD", flag, ..] => {
+                    dictionary.forbidden = parse_flags(flag, format).first().copied()
+                }
+                ["TRY", chars, ..] => dictionary.try_chars = chars.chars().collect(),
+                ["REP", from, to, ..] => dictionary
+                    .replacements
+                    .push((from.replace('_', " "), to.replace('_', " "))),
+                [kind @ ("PFX" | "SFX"), flag, combine, count]
+                    if count.parse::<usize>().is_ok() =>
+                {
+                    cross.insert((*kind, flag.to_string()), *combine == "Y");
+                }
+                [kind @ ("PFX" | "SFX"), flag, strip, add, condition, ..] => {
+                    let Some(&flag_value) = parse_flags(flag, format).first() else {
+                        continue;
+                    };
+                    let add = add.split('/').next().unwrap_or_default();
+                    let affix = Affix {
+                        flag: flag_value,
+                        cross: cross
+                            .get(&(*kind, flag.to_string()))
+                            .copied()
+                            .unwrap_or(false),
+                        strip: if *strip == "0" {
+                            String::new()
+                        } else {
+                            strip.to_string()
+                        },
+                        add: if add == "0" {
+                            String::new()
+                        } else {
+                            add.to_string()
+                        },
+                        condition: parse_condition(condition),
+                    };
+                    let table = if *kind == "PFX" {
+                        &mut dictionary.prefixes
+                    } else {
+                        &mut dictionary.suffixes
+                    };
+                    table.entry(affix.add.clone()).or_default().push(affix);
+                }
+                _ => {}
+            }
+        }
+
+        // A primeira linha do `.dic` é a contagem aproximada de palavras
+        for line in decode(&dic_bytes).lines().skip(1) {
+            let entry = line.split(['\t', ' ']).next().unwrap_or_default();
+            if entry.is_empty() {
+                continue;
+            }
+            let (word, flags) = match entry.split_once('/') {
+                Some((word, flags)) => (word, parse_flags(flags, format)),
+                None => (entry, Vec::new()),
+            };
+            dictionary
+                .words
+                .entry(word.to_string())
+                .or_default()
+                .extend(flags);
+        }
+        Ok(dictionary)
+    }
+
+    // A palavra existe como está, em minúsculas ou capitalizada
+    fn check(&self, word: &str) -> bool {
+        if self.lookup(word) {
+            return true;
+        }
+        let lower = word.to_lowercase();
+        if lower != word && self.lookup(&lower) {
+            return true;
+        }
+        let capitalized = capitalize(&lower);
+        capitalized != word && word.chars().all(|c| !c.is_lowercase()) && self.lookup(&capitalized)
+    }
+
+    fn lookup(&self, word: &str) -> bool {
+        if self.words.get(word).is_some_and(|flags| {
+            !self.has(flags, self.need_affix) && !self.has(flags, self.forbidden)
+        }) {
+            return true;
+        }
+        self.check_suffixes(word, None) || self.check_prefixes(word)
+    }
+
+    fn has(&self, flags: &[Flag], flag: Option<Flag>) -> bool {
+        flag.is_some_and(|flag| flags.contains(&flag))
+    }
+
+    // Raiz encontrada com o flag do afixo (e o do prefixo, quando combinados)
+    fn root_allows(&self, root: &str, flag: Flag, other: Option<Flag>) -> bool {
+        self.words.get(root).is_some_and(|flags| {
+            flags.contains(&flag)
+                && other.is_none_or(|other| flags.contains(&other))
+                && !self.has(flags, self.forbidden)
+        })
+    }
+
+    fn check_suffixes(&self, word: &str, prefix: Option<&Affix>) -> bool {
+        word.char_indices()
+            .map(|(index, _)| index)
+            .chain([word.len()])
+            .any(|index| {
+                let Some(rules) = self.suffixes.get(&word[index..]) else {
+                    return false;
+                };
+                rules.iter().any(|rule| {
+                    if prefix.is_some() && !rule.cross {
+                        return false;
+                    }
+                    let root = format!("{}{}", &word[..index], rule.strip);
+                    !root.is_empty()
+                        && rule.condition_matches(&root, false)
+                        && self.root_allows(&root, rule.flag, prefix.map(|p| p.flag))
+                })
+            })
+    }
+
+    fn check_prefixes(&self, word: &str) -> bool {
+        word.char_indices()
+            .map(|(index, _)| index)
+            .chain([word.len()])
+            .any(|index| {
+                let Some(rules) = self.prefixes.get(&word[..index]) else {
+                    return false;
+                };
+                rules.iter().any(|rule| {
+                    let root = format!("{}{}", rule.strip, &word[index..]);
+                    if root.is_empty() || !rule.condition_matches(&root, true) {
+                        return false;
+                    }
+                    self.root_allows(&root, rule.flag, None)
+                        || (rule.cross && self.check_suffixes(&root, Some(rule)))
+                })
+            })
+    }
+
+    // Candidatos a uma edição de distância (remoção, troca de vizinhos,
+    // substituição e inserção com os caracteres de TRY) e as trocas de REP
+    fn suggest(&self, word: &str) -> Vec<String> {
+        let chars: Vec<char> = word.chars().collect();
+        let alphabet: Vec<char> = if self.try_chars.is_empty() {
+            ('a'..='z').collect()
+        } else {
+            self.try_chars.clone()
+        };
+
+        let mut candidates = Vec::new();
+        for (from, to) in &self.replacements {
+            for (index, _) in word.match_indices(from.as_str()) {
+                candidates.push(format!(
+                    "{}{}{}",
+                    &word[..index],
+                    to,
+                    &word[index + from.len()..]
+                ));
+            }
+        }
+        for index in 0..chars.len() {
+            let mut swapped = chars.clone();
+            if index + 1 < chars.len() {
+                swapped.swap(index, index + 1);
+                candidates.push(swapped.iter().collect());
+            }
+            let mut removed = chars.clone();
+            removed.remove(index);
+            candidates.push(removed.iter().collect());
+            for &c in &alphabet {
+                let mut replaced = chars.clone();
+                replaced[index] = c;
+                candidates.push(replaced.iter().collect());
+            }
+        }
+        for index in 0..=chars.len() {
+            for &c in &alphabet {
+                let mut inserted = chars.clone();
+                inserted.insert(index, c);
+                candidates.push(inserted.iter().collect());
+            }
+        }
+
+        let capitalized = word.chars().next().is_some_and(char::is_uppercase);
+        let mut suggestions: Vec<String> = Vec::new();
+        for candidate in candidates {
+            if candidate == word || !self.check(&candidate) {
+                continue;
+            }
+            let candidate = if capitalized {
+                capitalize(&candidate)
+            } else {
+                candidate
+            };
+            if !suggestions.contains(&candidate) {
+                suggestions.push(candidate);
+                if suggestions.len() == SUGGESTIONS {
+                    break;
+                }
+            }
+        }
+        suggestions
+    }
+}
+
+fn parse_flags(flags: &str, format: FlagFormat) -> Vec<Flag> {
+    match format {
+        FlagFormat::Char => flags.chars().map(|c| c as Flag).collect(),
+        FlagFormat::Long => flags
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(2)
+            .map(|pair| pair.iter().fold(0, |value, &c| (value << 16) | c as Flag))
+            .collect(),
+        FlagFormat::Numeric => flags
+            .split(',')
+            .filter_map(|flag| flag.trim().parse().ok())
+            .collect(),
+    }
+}
+
+fn parse_condition(condition: &str) -> Vec<CharClass> {
+    let mut classes = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => classes.push(CharClass::Any),
+            '[' => {
+                let mut set = Vec::new();
+                let mut negated = false;
+                for c in chars.by_ref() {
+                    match c {
+                        ']' => break,
+                        '^' if set.is_empty() && !negated => negated = true,
+                        c => set.push(c),
+                    }
+                }
+                classes.push(CharClass::Set(set, negated));
+            }
+            c => classes.push(CharClass::Set(vec![c], false)),
+        }
+    }
+    classes
+}
+
+fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&byte| byte as char).collect()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
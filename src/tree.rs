@@ -0,0 +1,115 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::app::{Entry, ViewRow};
+
+enum Item {
+    Leaf(usize),
+    Child(usize),
+}
+
+struct Node {
+    path: String,
+    items: Vec<Item>,
+    translated: usize,
+    total: usize,
+}
+
+// Último segmento de uma chave ou namespace (`auth.login.title` -> `title`)
+pub fn leaf_name(key: &str) -> &str {
+    key.rsplit('.').next().unwrap_or(key)
+}
+
+// Caminhos dos namespaces que contêm a chave, do mais externo ao mais interno
+pub fn ancestors(key: &str) -> Vec<String> {
+    let segments: Vec<&str> = key.split('.').collect();
+    (1..segments.len())
+        .map(|n| segments[..n].join("."))
+        .collect()
+}
+
+// Agrupa as entradas visíveis por segmentos da chave. Cada namespace aparece na
+// posição da sua primeira entrada, então a ordenação atual é preservada dentro
+// dos grupos. Retorna a ordem completa das entradas e as linhas exibidas,
+// omitindo o conteúdo dos namespaces recolhidos.
+pub fn build(
+    entries: &[Entry],
+    view: &[usize],
+    collapsed: &HashSet<String>,
+) -> (Vec<usize>, Vec<ViewRow>) {
+    let mut nodes = vec![Node {
+        path: String::new(),
+        items: Vec::new(),
+        translated: 0,
+        total: 0,
+    }];
+    let mut by_path: HashMap<String, usize> = HashMap::new();
+
+    for &index in view {
+        let entry = &entries[index];
+        let mut current = 0;
+        for path in ancestors(&entry.key) {
+            let node = match by_path.get(&path) {
+                Some(&node) => node,
+                None => {
+                    nodes.push(Node {
+                        path: path.clone(),
+                        items: Vec::new(),
+                        translated: 0,
+                        total: 0,
+                    });
+                    let node = nodes.len() - 1;
+                    nodes[current].items.push(Item::Child(node));
+                    by_path.insert(path, node);
+                    node
+                }
+            };
+            if !entry.is_locked() {
+                nodes[node].total += 1;
+                if entry.is_translated() {
+                    nodes[node].translated += 1;
+                }
+            }
+            current = node;
+        }
+        nodes[current].items.push(Item::Leaf(index));
+    }
+
+    let mut order = Vec::with_capacity(view.len());
+    let mut rows = Vec::new();
+    flatten(&nodes, 0, 0, true, collapsed, &mut order, &mut rows);
+    (order, rows)
+}
+
+fn flatten(
+    nodes: &[Node],
+    node: usize,
+    depth: usize,
+    visible: bool,
+    collapsed: &HashSet<String>,
+    order: &mut Vec<usize>,
+    rows: &mut Vec<ViewRow>,
+) {
+    for item in &nodes[node].items {
+        match *item {
+            Item::Leaf(index) => {
+                order.push(index);
+                if visible {
+                    rows.push(ViewRow::Entry { index, depth });
+                }
+            }
+            Item::Child(child) => {
+                let child_node = &nodes[child];
+                if visible {
+                    rows.push(ViewRow::Namespace {
+                        path: child_node.path.clone(),
+                        depth,
+                        translated: child_node.translated,
+                        total: child_node.total,
+                    });
+                }
+                let expanded = visible && !collapsed.contains(&child_node.path);
+                flatten(nodes, child, depth + 1, expanded, collapsed, order, rows);
+            }
+        }
+    }
+}
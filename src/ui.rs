@@ -1,21 +1,35 @@
 use std::io;
+use std::ops::Range;
 use tui::{
     Frame,
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Span, Spans},
     widgets::{Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table, TableState, Wrap},
 };
 
-use crate::app::{App, AppState};
+use crate::app::{App, AppState, Entry, EntryStatus, Lock, SortMode, ViewRow};
+use crate::find_replace::{DiffSegment, ReplaceField, ReplaceState};
+use crate::formats::registry;
+use crate::glossary::Glossary;
+use crate::review::ReviewState;
+use crate::spelling::{SpellCache, Speller};
+use crate::tree;
+use crate::validation;
 
 pub fn render(frame: &mut Frame<CrosstermBackend<io::Stdout>>, app: &mut App) {
     match app.state {
         AppState::FileSelection => render_file_selection(frame, app),
         AppState::Editing => {
             if let Some(editing) = &mut app.editing {
-                render_editing(frame, editing, app.locale.get("translation_title"), &app.locale)
+                render_editing(
+                    frame,
+                    editing,
+                    app.locale.get("translation_title"),
+                    &app.locale,
+                    &app.speller,
+                )
             }
         }
         AppState::SaveConfirmation => {
@@ -23,6 +37,7 @@ pub fn render(frame: &mut Frame<CrosstermBackend<io::Stdout>>, app: &mut App) {
                 render_save_confirmation(frame, confirmation, app)
             }
         }
+        AppState::Memory => render_memory(frame, app),
         AppState::Exiting => (),
     }
 }
@@ -37,48 +52,51 @@ pub fn render_file_selection(frame: &mut Frame<CrosstermBackend<io::Stdout>>, ap
         .file_selection
         .files
         .iter()
-        .map(|f| {
-            ListItem::new(
-                f.file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string(),
-            )
-        })
+        // Caminho relativo: arquivos de subpastas diferentes podem ter o mesmo nome
+        .map(|f| ListItem::new(f.display().to_string()))
         .collect();
 
+    let title = app
+        .locale
+        .get_with_params("file_selection_title", &[("formats", &registry::names())]);
+
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(app.locale.get("file_selection_title")),
-        )
+        .block(Block::default().borders(Borders::ALL).title(title))
         .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black));
 
     frame.render_stateful_widget(list, chunks[0], &mut app.file_selection.list_state);
 
-    let help = Paragraph::new(vec![Spans::from(vec![
-        Span::raw(app.locale.get("help_navigation")),
-        Span::styled(
-            app.locale.get("up_down_keys"),
-            Style::default().fg(Color::Yellow),
-        ),
-        Span::raw(app.locale.get("select_help")),
-        Span::styled(
-            app.locale.get("language_key"),
-            Style::default().fg(Color::Yellow),
-        ),
-        Span::raw(app.locale.get("language_help")),
-        Span::styled(
-            app.locale.get("enter_key"),
-            Style::default().fg(Color::Green),
-        ),
-        Span::raw(app.locale.get("open_help")),
-        Span::styled(app.locale.get("quit_key"), Style::default().fg(Color::Red)),
-        Span::raw(app.locale.get("quit_help")),
-    ])])
-    .block(Block::default().borders(Borders::TOP))
-    .wrap(Wrap { trim: true });
+    let help = match &app.file_selection.message {
+        Some(message) => Spans::from(Span::styled(
+            message.clone(),
+            Style::default().fg(Color::Red),
+        )),
+        None => Spans::from(vec![
+            Span::raw(app.locale.get("help_navigation")),
+            Span::styled(
+                app.locale.get("up_down_keys"),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(app.locale.get("select_help")),
+            Span::styled(
+                app.locale.get("language_key"),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(app.locale.get("language_help")),
+            Span::styled(
+                app.locale.get("enter_key"),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw(app.locale.get("open_help")),
+            Span::styled("M", Style::default().fg(Color::Yellow)),
+            Span::raw(app.locale.get("memory_screen_help")),
+            Span::styled(app.locale.get("quit_key"), Style::default().fg(Color::Red)),
+            Span::raw(app.locale.get("quit_help")),
+        ]),
+    };
+    let help = Paragraph::new(vec![help])
+        .block(Block::default().borders(Borders::TOP))
+        .wrap(Wrap { trim: true });
 
     frame.render_widget(help, chunks[1]);
 }
@@ -88,6 +106,7 @@ pub fn render_editing(
     state: &mut crate::app::EditingState,
     title_template: &str,
     locale: &crate::localization::Locale,
+    speller: &Speller,
 ) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -95,11 +114,20 @@ pub fn render_editing(
         .constraints([
             Constraint::Min(3),
             Constraint::Length(3),
-            Constraint::Length(3),
+            Constraint::Length(5),
             Constraint::Length(3),
         ])
         .split(frame.size());
 
+    let main_area = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(72), Constraint::Percentage(28)])
+        .split(chunks[0]);
+    let (table_area, details_area) = (main_area[0], main_area[1]);
+
+    // Bordas e cabeçalho ocupam três linhas da tabela
+    state.page_size = table_area.height.saturating_sub(3) as usize;
+
     let mut title = title_template.to_string();
     let params = [
         ("translated", state.translated_keys.to_string()),
@@ -108,8 +136,30 @@ pub fn render_editing(
     for (k, v) in &params {
         title = title.replace(&format!("{{{}}}", k), v);
     }
+    if state.sort_mode != SortMode::Original {
+        title.push_str(&locale.get_with_params(
+            "sorted_title",
+            &[("mode", locale.get(state.sort_mode.label_key()))],
+        ));
+    }
+    if !state.marked.is_empty() {
+        title.push_str(&locale.get_with_params(
+            "marked_title",
+            &[("count", &state.marked.len().to_string())],
+        ));
+    }
+    if state.filter.is_active() {
+        title.push_str(&locale.get_with_params(
+            "filtered_title",
+            &[("shown", &state.view.len().to_string())],
+        ));
+    }
 
-    if state.search_mode && !state.search_query.is_empty() {
+    if let Some(review) = &state.review {
+        render_review(frame, table_area, state, review, locale);
+    } else if let Some(replace) = state.replace.as_ref().filter(|r| r.preview) {
+        render_replace_preview(frame, table_area, state, replace, locale);
+    } else if state.search_mode && !state.search_query.is_empty() {
         let rows: Vec<Row> = state
             .search_results
             .iter()
@@ -117,11 +167,7 @@ pub fn render_editing(
             .map(|(view_index, &entry_index)| {
                 let entry = &state.entries[entry_index];
                 
-                let key_style = if entry.is_translated {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default()
-                };
+                let key_style = entry_style(entry);
 
                 let style = if state.search_selection == Some(view_index) {
                     Style::default().bg(Color::Blue)
@@ -131,8 +177,8 @@ pub fn render_editing(
 
                 Row::new(vec![
                     Cell::from(Span::styled(entry.key.clone(), key_style)),
-                    Cell::from(format_json_value(&entry.original)),
-                    Cell::from(format_json_value(&entry.translated)),
+                    Cell::from(highlight_terms(format_json_value(&entry.original), &state.glossary)),
+                    Cell::from(translated_spans(entry, entry_index, &mut state.spelling, speller)),
                 ])
                 .style(style)
             })
@@ -153,30 +199,77 @@ pub fn render_editing(
 
         let mut temp_state = TableState::default();
         temp_state.select(state.search_selection);
-        frame.render_stateful_widget(table, chunks[0], &mut temp_state);
+        frame.render_stateful_widget(table, table_area, &mut temp_state);
 
     } else {
         let rows: Vec<Row> = state
-            .entries
+            .rows
             .iter()
             .enumerate()
-            .map(|(i, entry)| {
-                let key_style = if entry.is_translated {
-                    Style::default().fg(Color::Green)
+            .map(|(i, view_row)| {
+                let marked = matches!(
+                    view_row,
+                    ViewRow::Entry { index, .. } if state.marked.contains(index)
+                );
+                let style = if state.table_state.selected() == Some(i) {
+                    Style::default().bg(Color::Blue)
+                } else if marked {
+                    Style::default().bg(Color::DarkGray)
                 } else {
                     Style::default()
                 };
 
-                let style = if state.table_state.selected() == Some(i) {
-                    Style::default().bg(Color::Blue)
+                let (entry_index, depth) = match view_row {
+                    ViewRow::Entry { index, depth } => (*index, *depth),
+                    ViewRow::Namespace {
+                        path,
+                        depth,
+                        translated,
+                        total,
+                    } => {
+                        let marker = if state.collapsed.contains(path) { "▸" } else { "▾" };
+                        let progress_style = if translated == total {
+                            Style::default().fg(Color::Green)
+                        } else {
+                            Style::default().fg(Color::Yellow)
+                        };
+                        return Row::new(vec![
+                            Cell::from(Span::styled(
+                                format!("{}{} {}", "  ".repeat(*depth), marker, tree::leaf_name(path)),
+                                Style::default().fg(Color::Cyan),
+                            )),
+                            Cell::from(Span::styled(
+                                format!("{}/{}", translated, total),
+                                progress_style,
+                            )),
+                            Cell::from(""),
+                        ])
+                        .style(style);
+                    }
+                };
+
+                let entry = &state.entries[entry_index];
+                let key_style = entry_style(entry);
+
+                let key_text = if state.tree_mode {
+                    format!("{}{}", "  ".repeat(depth), tree::leaf_name(&entry.key))
                 } else {
-                    Style::default()
+                    entry.key.clone()
+                };
+
+                let key_cell = if validation::has_issues(entry, &state.glossary) {
+                    Spans::from(vec![
+                        Span::styled("⚠ ", Style::default().fg(Color::Yellow)),
+                        Span::styled(key_text, key_style),
+                    ])
+                } else {
+                    Spans::from(Span::styled(key_text, key_style))
                 };
 
                 Row::new(vec![
-                    Cell::from(Span::styled(entry.key.clone(), key_style)),
-                    Cell::from(format_json_value(&entry.original)),
-                    Cell::from(format_json_value(&entry.translated)),
+                    Cell::from(key_cell),
+                    Cell::from(highlight_terms(format_json_value(&entry.original), &state.glossary)),
+                    Cell::from(translated_spans(entry, entry_index, &mut state.spelling, speller)),
                 ])
                 .style(style)
             })
@@ -195,9 +288,19 @@ pub fn render_editing(
                 Constraint::Percentage(40),
             ]);
 
-        frame.render_stateful_widget(table, chunks[0], &mut state.table_state);
+        frame.render_stateful_widget(table, table_area, &mut state.table_state);
     };
 
+    // Sugestões só da entrada selecionada, refeitas quando a tradução muda
+    let suggestions = match state.selected_index() {
+        Some(index) if !state.entries[index].is_locked() => {
+            let text = format_json_value(&state.entries[index].translated);
+            state.spelling.suggestions(index, &text, speller).to_vec()
+        }
+        _ => Vec::new(),
+    };
+    render_details(frame, details_area, state, locale, &suggestions);
+
     // --- CÁLCULO DO SCROLL HORIZONTAL ---
     
     // 1. Calculamos a largura interna da caixa (largura total - 2 caracteres das bordas)
@@ -222,18 +325,28 @@ pub fn render_editing(
         start
     };
 
-    // 4. Criamos a string visível baseada no offset calculado
-    let visible_input: String = chars
-        .iter()
-        .skip(scroll_offset)
-        .take(inner_width)
-        .collect();
+    // 4. Criamos o trecho visível baseado no offset calculado, com as palavras
+    // desconhecidas sublinhadas
+    let misspelled = misspelled_chars(&state.input, state.spelling.input(&state.input, speller));
+    let visible = scroll_offset..(scroll_offset + inner_width).min(total_len);
+    let visible_input = spelling_spans(&chars[visible.clone()], &misspelled[visible]);
 
-    // 5. Renderiza apenas o texto visível
+    // 5. Renderiza apenas o texto visível, com o contador de caracteres no
+    // título quando a entrada tem limite de tamanho
+    let mut title = vec![Span::raw(locale.get("edit_value_title"))];
+    if let Some(max) = state.editing.and_then(|index| state.entries[index].max_length) {
+        let length = state.input.chars().count();
+        let style = if length > max {
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Green)
+        };
+        title.push(Span::styled(format!(" {}/{} ", length, max), style));
+    }
     let input = Paragraph::new(visible_input).block(
         Block::default()
             .borders(Borders::ALL)
-            .title(locale.get("edit_value_title")),
+            .title(Spans::from(title)),
     );
     frame.render_widget(input, chunks[1]);
 
@@ -248,65 +361,170 @@ pub fn render_editing(
 
     // ------------------------------------
 
-    let help_text = if state.editing.is_some() {
+    let preview_help = || {
         vec![Spans::from(vec![
             Span::styled(
-                locale.get("cursor_key"),
+                locale.get("up_down_keys"),
                 Style::default().fg(Color::Yellow),
             ),
-            Span::raw(locale.get("cursor_help")),
+            Span::raw(locale.get("search_navigate_help")),
+            Span::styled(locale.get("space_key"), Style::default().fg(Color::Magenta)),
+            Span::raw(locale.get("toggle_row_help")),
+            Span::styled("A", Style::default().fg(Color::LightGreen)),
+            Span::raw(locale.get("accept_all_help")),
             Span::styled(
                 locale.get("enter_key"),
                 Style::default().fg(Color::Green),
             ),
-            Span::raw(locale.get("confirm_help")),
+            Span::raw(locale.get("apply_selected_help")),
             Span::styled(locale.get("esc_key"), Style::default().fg(Color::Red)),
-            Span::raw(locale.get("cancel_help")),
+            Span::raw(locale.get("back_help")),
         ])]
-    } else if state.search_mode {
+    };
+
+    let help_text = if state.review.is_some() {
+        preview_help()
+    } else if let Some(replace) = &state.replace {
+        if replace.preview {
+            preview_help()
+        } else {
+            vec![Spans::from(vec![
+                Span::styled(locale.get("tab_key"), Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("switch_field_help")),
+                Span::styled(
+                    locale.get("enter_key"),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::raw(locale.get("preview_help")),
+                Span::styled(locale.get("esc_key"), Style::default().fg(Color::Red)),
+                Span::raw(locale.get("cancel_help")),
+            ])]
+        }
+    } else if state.editing.is_some() {
         vec![Spans::from(vec![
             Span::styled(
-                locale.get("up_down_keys"),
+                locale.get("cursor_key"),
                 Style::default().fg(Color::Yellow),
             ),
-            Span::raw(locale.get("search_navigate_help")),
+            Span::raw(locale.get("cursor_help")),
             Span::styled(
                 locale.get("enter_key"),
                 Style::default().fg(Color::Green),
             ),
-            Span::raw(locale.get("select_help")),
+            Span::raw(locale.get("confirm_help")),
             Span::styled(locale.get("esc_key"), Style::default().fg(Color::Red)),
             Span::raw(locale.get("cancel_help")),
         ])]
-    } else {
+    } else if state.search_mode {
         vec![Spans::from(vec![
-            Span::raw(locale.get("navigation_help")),
             Span::styled(
                 locale.get("up_down_keys"),
                 Style::default().fg(Color::Yellow),
             ),
-            Span::raw(locale.get("select_help")),
-            Span::styled(
-                locale.get("language_key"),
-                Style::default().fg(Color::Yellow),
-            ),
-            Span::raw(locale.get("language_help")),
+            Span::raw(locale.get("search_navigate_help")),
             Span::styled(
                 locale.get("enter_key"),
                 Style::default().fg(Color::Green),
             ),
-            Span::raw(locale.get("edit_help")),
-            Span::styled("T", Style::default().fg(Color::Magenta)),
-            Span::raw(locale.get("mark_translated_help")),
-            Span::styled("B", Style::default().fg(Color::LightGreen)),
-            Span::raw(locale.get("save_help")),
-            Span::styled(locale.get("esc_key"), Style::default().fg(Color::Blue)),
-            Span::raw(locale.get("save_return_help")),
-            Span::styled("Q", Style::default().fg(Color::Red)),
-            Span::raw(locale.get("save_quit_help")),
-            Span::styled("S", Style::default().fg(Color::Cyan)),
-            Span::raw(locale.get("search_help")),
+            Span::raw(locale.get("select_help")),
+            Span::styled(locale.get("esc_key"), Style::default().fg(Color::Red)),
+            Span::raw(locale.get("cancel_help")),
         ])]
+    } else {
+        vec![
+            Spans::from(vec![
+                Span::raw(locale.get("navigation_help")),
+                Span::styled(
+                    locale.get("up_down_keys"),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(locale.get("select_help")),
+                Span::styled(
+                    locale.get("language_key"),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(locale.get("language_help")),
+                Span::styled(
+                    locale.get("enter_key"),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::raw(locale.get("edit_help")),
+                Span::styled("T", Style::default().fg(Color::Magenta)),
+                Span::raw(locale.get("mark_translated_help")),
+                Span::styled("B", Style::default().fg(Color::LightGreen)),
+                Span::raw(locale.get("save_help")),
+                Span::styled(locale.get("esc_key"), Style::default().fg(Color::Blue)),
+                Span::raw(locale.get("save_return_help")),
+                Span::styled("Q", Style::default().fg(Color::Red)),
+                Span::raw(locale.get("save_quit_help")),
+                Span::styled("R", Style::default().fg(Color::LightMagenta)),
+                Span::raw(locale.get("replace_help")),
+                Span::styled("U", Style::default().fg(Color::LightYellow)),
+                Span::raw(locale.get("undo_help")),
+                Span::styled("F", Style::default().fg(Color::LightBlue)),
+                Span::raw(locale.get("filter_help")),
+                Span::styled("S", Style::default().fg(Color::Cyan)),
+                Span::raw(locale.get("search_help")),
+            ]),
+            Spans::from(vec![
+                Span::styled(locale.get("tab_key"), Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("next_untranslated_help")),
+                Span::styled("]/[", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("next_issue_help")),
+                Span::styled(">/<", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("next_dirty_help")),
+                Span::styled(locale.get("page_keys"), Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("page_help")),
+                Span::styled("G", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("goto_help")),
+                Span::styled("O", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("sort_help")),
+                Span::styled("V", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get(if state.tree_mode {
+                    "tree_view_on_help"
+                } else {
+                    "tree_view_off_help"
+                })),
+                Span::styled("A", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get(if state.auto_advance {
+                    "auto_advance_on_help"
+                } else {
+                    "auto_advance_off_help"
+                })),
+            ]),
+            Spans::from(vec![
+                Span::styled(locale.get("mark_keys"), Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("mark_help")),
+                Span::styled("C", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("copy_source_help")),
+                Span::styled("N", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("skeleton_help")),
+                Span::styled("X", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("clear_translation_help")),
+                Span::styled("Z", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("lock_help")),
+                Span::styled("W", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("cycle_status_help")),
+                Span::styled("E", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("xliff_export_help")),
+                Span::styled("I", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("xliff_import_help")),
+                Span::styled("K", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("spreadsheet_export_help")),
+                Span::styled("L", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("spreadsheet_import_help")),
+                Span::styled("M", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("memory_apply_help")),
+                Span::styled("P", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("mt_prefill_help")),
+                Span::styled("D", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("mt_batch_help")),
+                Span::styled("H", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("hook_help")),
+                Span::styled("J", Style::default().fg(Color::Yellow)),
+                Span::raw(locale.get("spelling_add_help")),
+            ]),
+        ]
     };
 
     let help = Paragraph::new(help_text)
@@ -315,7 +533,35 @@ pub fn render_editing(
 
     frame.render_widget(help, chunks[2]);
 
-    if state.search_mode {
+    if let Some(replace) = &state.replace {
+        let field_style = |field: ReplaceField| {
+            if replace.field == field && !replace.preview {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            }
+        };
+        let title = match &replace.error {
+            Some(error) => Span::styled(error.clone(), Style::default().fg(Color::Red)),
+            None => Span::raw(locale.get("replace_title")),
+        };
+
+        let replace_bar = Paragraph::new(Spans::from(vec![
+            Span::raw(locale.get("replace_find_label")),
+            Span::styled(replace.find.clone(), field_style(ReplaceField::Find)),
+            Span::raw(locale.get("replace_with_label")),
+            Span::styled(replace.replace.clone(), field_style(ReplaceField::Replace)),
+        ]))
+        .block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(replace_bar, chunks[3]);
+    } else if state.goto_mode {
+        let goto_bar = Paragraph::new(state.goto_input.clone()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(locale.get("goto_title")),
+        );
+        frame.render_widget(goto_bar, chunks[3]);
+    } else if state.search_mode {
         let mut search_text = locale.get("search_results").to_string();
         search_text = search_text.replace("{query}", &state.search_query);
         search_text = search_text.replace("{count}", &state.search_results.len().to_string());
@@ -327,12 +573,347 @@ pub fn render_editing(
         );
         frame.render_widget(search_bar, chunks[3]);
     } else if state.save_notification.is_some() {
-        let notification = Paragraph::new(locale.get("save_success"))
+        let text = state
+            .notification_message
+            .as_deref()
+            .unwrap_or(locale.get("save_success"));
+        let notification = Paragraph::new(text)
             .style(Style::default().fg(Color::Green))
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::ALL));
         frame.render_widget(notification, chunks[3]);
     }
+
+    if state.filter_mode {
+        render_filter_popup(frame, state, locale);
+    }
+}
+
+// Painel lateral com o contexto da entrada selecionada: notas do arquivo de
+// origem e problemas encontrados pelo validador.
+fn render_details(
+    frame: &mut Frame<CrosstermBackend<io::Stdout>>,
+    area: Rect,
+    state: &crate::app::EditingState,
+    locale: &crate::localization::Locale,
+    suggestions: &[(String, Vec<String>)],
+) {
+    let mut lines = Vec::new();
+
+    if let Some(index) = state.selected_index() {
+        let entry = &state.entries[index];
+        lines.push(Spans::from(Span::styled(
+            entry.key.clone(),
+            entry_style(entry),
+        )));
+        match entry.lock {
+            Lock::Manual => lines.push(Spans::from(Span::styled(
+                locale.get("locked_label"),
+                Style::default().fg(Color::DarkGray),
+            ))),
+            Lock::Config => lines.push(Spans::from(Span::styled(
+                locale.get("locked_config_label"),
+                Style::default().fg(Color::DarkGray),
+            ))),
+            Lock::Unlocked => {}
+        }
+        if let Some(max) = entry.max_length {
+            lines.push(Spans::from(Span::styled(
+                locale.get_with_params("length_limit", &[("max", &max.to_string())]),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        if !entry.notes.is_empty() {
+            lines.push(Spans::from(""));
+            lines.push(Spans::from(Span::styled(
+                locale.get("notes_title"),
+                Style::default().fg(Color::Cyan),
+            )));
+            lines.extend(entry.notes.iter().map(|note| Spans::from(note.clone())));
+        }
+
+        let terms = state.glossary.terms_in(&format_json_value(&entry.original));
+        if !terms.is_empty() {
+            lines.push(Spans::from(""));
+            lines.push(Spans::from(Span::styled(
+                locale.get("glossary_title"),
+                Style::default().fg(Color::Cyan),
+            )));
+            for term in terms {
+                let mut spans = vec![
+                    Span::styled(term.source.clone(), Style::default().fg(Color::Yellow)),
+                    Span::raw(" → "),
+                    Span::raw(term.targets.join(" / ")),
+                ];
+                if !term.forbidden.is_empty() {
+                    spans.push(Span::styled(
+                        format!("  ✗ {}", term.forbidden.join(", ")),
+                        Style::default().fg(Color::Red),
+                    ));
+                }
+                lines.push(Spans::from(spans));
+                if let Some(note) = &term.note {
+                    lines.push(Spans::from(Span::styled(
+                        format!("  {}", note),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            }
+        }
+
+        if !suggestions.is_empty() {
+            lines.push(Spans::from(""));
+            lines.push(Spans::from(Span::styled(
+                locale.get("spelling_title"),
+                Style::default().fg(Color::Cyan),
+            )));
+            for (word, suggestions) in suggestions {
+                lines.push(Spans::from(vec![
+                    Span::styled(
+                        word.clone(),
+                        Style::default().fg(Color::Red).add_modifier(Modifier::UNDERLINED),
+                    ),
+                    Span::raw(" → "),
+                    if suggestions.is_empty() {
+                        Span::styled(
+                            locale.get("spelling_no_suggestions"),
+                            Style::default().fg(Color::DarkGray),
+                        )
+                    } else {
+                        Span::raw(suggestions.join(", "))
+                    },
+                ]));
+            }
+        }
+
+        if state.memory_for == Some(index) && !state.memory_matches.is_empty() {
+            lines.push(Spans::from(""));
+            lines.push(Spans::from(Span::styled(
+                locale.get("memory_title"),
+                Style::default().fg(Color::Cyan),
+            )));
+            for found in &state.memory_matches {
+                let file = std::path::Path::new(&found.file)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                lines.push(Spans::from(vec![
+                    Span::styled(
+                        format!("{}% ", found.score),
+                        Style::default().fg(Color::Green),
+                    ),
+                    Span::raw(found.target.clone()),
+                ]));
+                lines.push(Spans::from(Span::styled(
+                    format!("  {} · {}", found.source, file),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+        }
+
+        let issues = validation::validate(entry, &state.glossary);
+        if !issues.is_empty() {
+            lines.push(Spans::from(""));
+            lines.push(Spans::from(Span::styled(
+                locale.get("issues_title"),
+                Style::default().fg(Color::Yellow),
+            )));
+            lines.extend(issues.iter().map(|issue| {
+                Spans::from(Span::styled(
+                    format!("⚠ {}", issue.describe(locale)),
+                    Style::default().fg(Color::Yellow),
+                ))
+            }));
+        }
+    }
+
+    let details = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(locale.get("details_title")),
+        )
+        .wrap(Wrap { trim: false });
+    frame.render_widget(details, area);
+}
+
+fn render_filter_popup(
+    frame: &mut Frame<CrosstermBackend<io::Stdout>>,
+    state: &crate::app::EditingState,
+    locale: &crate::localization::Locale,
+) {
+    let filter = &state.filter;
+    let options = [
+        ('1', "filter_untranslated", filter.untranslated),
+        ('2', "filter_translated", filter.translated),
+        ('3', "filter_dirty", filter.dirty),
+        ('4', "filter_issues", filter.issues),
+        ('5', "filter_empty", filter.empty),
+        ('6', "filter_identical", filter.identical),
+    ];
+
+    let mut items: Vec<ListItem> = options
+        .iter()
+        .map(|(number, label, enabled)| {
+            let mark = if *enabled { "[x]" } else { "[ ]" };
+            ListItem::new(Spans::from(vec![
+                Span::styled(number.to_string(), Style::default().fg(Color::Yellow)),
+                Span::raw(format!(" {} {}", mark, locale.get(label))),
+            ]))
+        })
+        .collect();
+
+    let prefix_style = if state.filter_prefix_input {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    items.push(ListItem::new(Spans::from(vec![
+        Span::styled("7", Style::default().fg(Color::Yellow)),
+        Span::raw(format!(" {}", locale.get("filter_prefix"))),
+        Span::styled(filter.prefix.clone(), prefix_style),
+    ])));
+    items.push(ListItem::new(Spans::from(vec![
+        Span::styled("0", Style::default().fg(Color::Yellow)),
+        Span::raw(format!(" {}", locale.get("filter_clear"))),
+    ])));
+
+    let title = locale.get_with_params(
+        "filter_title",
+        &[
+            ("shown", &state.view.len().to_string()),
+            ("total", &state.entries.len().to_string()),
+        ],
+    );
+
+    let area = centered_rect(50, 50, frame.size());
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        List::new(items).block(Block::default().borders(Borders::ALL).title(title)),
+        area,
+    );
+}
+
+fn render_replace_preview(
+    frame: &mut Frame<CrosstermBackend<io::Stdout>>,
+    area: Rect,
+    state: &crate::app::EditingState,
+    replace: &ReplaceState,
+    locale: &crate::localization::Locale,
+) {
+    let diff_spans = |segments: &[DiffSegment], color: Color| {
+        Spans::from(
+            segments
+                .iter()
+                .map(|segment| {
+                    if segment.changed {
+                        Span::styled(segment.text.clone(), Style::default().fg(color))
+                    } else {
+                        Span::raw(segment.text.clone())
+                    }
+                })
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    let rows: Vec<Row> = replace
+        .matches
+        .iter()
+        .map(|found| {
+            let mark = if found.accepted { "[x]" } else { "[ ]" };
+            Row::new(vec![
+                Cell::from(mark),
+                Cell::from(state.entries[found.entry_index].key.clone()),
+                Cell::from(diff_spans(&found.before, Color::Red)),
+                Cell::from(diff_spans(&found.after, Color::Green)),
+            ])
+        })
+        .collect();
+
+    let title = locale.get_with_params(
+        "replace_preview_title",
+        &[("count", &replace.matches.len().to_string())],
+    );
+
+    let table = Table::new(rows)
+        .header(Row::new(vec![
+            "",
+            locale.get("header_key"),
+            locale.get("header_before"),
+            locale.get("header_after"),
+        ]))
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().bg(Color::Blue))
+        .widths(&[
+            Constraint::Length(3),
+            Constraint::Percentage(23),
+            Constraint::Percentage(37),
+            Constraint::Percentage(37),
+        ]);
+
+    let mut table_state = TableState::default();
+    if !replace.matches.is_empty() {
+        table_state.select(Some(replace.selection));
+    }
+    frame.render_stateful_widget(table, area, &mut table_state);
+}
+
+// Memória de tradução: segmentos por par de idiomas, com importação e
+// exportação TMX e remoção
+fn render_memory(frame: &mut Frame<CrosstermBackend<io::Stdout>>, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
+        .split(frame.size());
+
+    let locale = &app.locale;
+    let screen = &mut app.memory_screen;
+    let mut items = vec![ListItem::new(locale.get_with_params(
+        "memory_all_pairs",
+        &[("count", &app.memory.len().to_string())],
+    ))];
+    items.extend(screen.pairs.iter().map(|(source, target, count)| {
+        ListItem::new(format!("{} → {} ({})", source, target, count))
+    }));
+
+    let title = locale.get_with_params(
+        "memory_screen_title",
+        &[("path", &app.config.tmx_path)],
+    );
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black));
+    frame.render_stateful_widget(list, chunks[0], &mut screen.list_state);
+
+    let help = match &screen.message {
+        Some(message) => {
+            let color = if screen.confirm.is_some() {
+                Color::Red
+            } else {
+                Color::Green
+            };
+            Spans::from(Span::styled(message.clone(), Style::default().fg(color)))
+        }
+        None => Spans::from(vec![
+            Span::styled(locale.get("up_down_keys"), Style::default().fg(Color::Yellow)),
+            Span::raw(locale.get("select_help")),
+            Span::styled("E", Style::default().fg(Color::Yellow)),
+            Span::raw(locale.get("tmx_export_help")),
+            Span::styled("I", Style::default().fg(Color::Yellow)),
+            Span::raw(locale.get("tmx_import_help")),
+            Span::styled("D", Style::default().fg(Color::Red)),
+            Span::raw(locale.get("purge_help")),
+            Span::styled("O", Style::default().fg(Color::Red)),
+            Span::raw(locale.get("purge_orphaned_help")),
+            Span::styled(locale.get("esc_key"), Style::default().fg(Color::Red)),
+            Span::raw(locale.get("back_help")),
+        ]),
+    };
+    let help = Paragraph::new(vec![help])
+        .block(Block::default().borders(Borders::TOP))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(help, chunks[1]);
 }
 
 pub fn render_save_confirmation(
@@ -420,6 +1001,175 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+// Prévia de uma importação: tradução atual e recebida, e a mudança de estado
+fn render_review(
+    frame: &mut Frame<CrosstermBackend<io::Stdout>>,
+    area: Rect,
+    state: &crate::app::EditingState,
+    review: &ReviewState,
+    locale: &crate::localization::Locale,
+) {
+    let rows: Vec<Row> = review
+        .changes
+        .iter()
+        .map(|change| {
+            let entry = &state.entries[change.entry_index];
+            let mark = if change.accepted { "[x]" } else { "[ ]" };
+            let current = format_json_value(&entry.translated);
+            let (before, after) = match &change.translated {
+                Some(value) => (
+                    Span::styled(current, Style::default().fg(Color::Red)),
+                    Span::styled(format_json_value(value), Style::default().fg(Color::Green)),
+                ),
+                None => (Span::raw(current.clone()), Span::raw(current)),
+            };
+            let status = change.status.unwrap_or(entry.status);
+            let status = Spans::from(vec![
+                Span::styled(locale.get(status_label(entry.status)), status_style(entry.status)),
+                Span::raw(" → "),
+                Span::styled(locale.get(status_label(status)), status_style(status)),
+            ]);
+            Row::new(vec![
+                Cell::from(mark),
+                Cell::from(entry.key.clone()),
+                Cell::from(before),
+                Cell::from(after),
+                Cell::from(status),
+            ])
+        })
+        .collect();
+
+    let title = locale.get_with_params(
+        "review_title",
+        &[
+            ("count", &review.changes.len().to_string()),
+            ("unmatched", &review.unmatched.len().to_string()),
+        ],
+    );
+
+    let table = Table::new(rows)
+        .header(Row::new(vec![
+            "",
+            locale.get("header_key"),
+            locale.get("header_before"),
+            locale.get("header_after"),
+            locale.get("header_status"),
+        ]))
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().bg(Color::Blue))
+        .widths(&[
+            Constraint::Length(3),
+            Constraint::Percentage(20),
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+            Constraint::Percentage(17),
+        ]);
+
+    let mut table_state = TableState::default();
+    if !review.changes.is_empty() {
+        table_state.select(Some(review.selection));
+    }
+    frame.render_stateful_widget(table, area, &mut table_state);
+}
+
+fn status_label(status: EntryStatus) -> &'static str {
+    match status {
+        EntryStatus::Untranslated => "status_untranslated",
+        EntryStatus::NeedsReview => "status_review",
+        EntryStatus::Translated => "status_translated",
+        EntryStatus::Draft => "status_draft",
+    }
+}
+
+// Texto de origem com os termos do glossário destacados
+fn highlight_terms(text: String, glossary: &Glossary) -> Spans<'static> {
+    let ranges = glossary.highlights(&text);
+    if ranges.is_empty() {
+        return Spans::from(text);
+    }
+    let mut spans = Vec::new();
+    let mut position = 0;
+    for range in ranges {
+        spans.push(Span::raw(text[position..range.start].to_string()));
+        spans.push(Span::styled(
+            text[range.clone()].to_string(),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::UNDERLINED),
+        ));
+        position = range.end;
+    }
+    spans.push(Span::raw(text[position..].to_string()));
+    Spans::from(spans)
+}
+
+// Entradas bloqueadas aparecem apagadas, com o texto de origem que será gravado
+fn entry_style(entry: &Entry) -> Style {
+    if entry.is_locked() {
+        Style::default().fg(Color::DarkGray)
+    } else {
+        status_style(entry.status)
+    }
+}
+
+fn translated_spans(
+    entry: &Entry,
+    index: usize,
+    spelling: &mut SpellCache,
+    speller: &Speller,
+) -> Spans<'static> {
+    if entry.is_locked() {
+        Spans::from(Span::styled(
+            format!("🔒 {}", format_json_value(&entry.original)),
+            Style::default().fg(Color::DarkGray),
+        ))
+    } else {
+        let text = format_json_value(&entry.translated);
+        let chars: Vec<char> = text.chars().collect();
+        let misspelled = spelling.misspelled(index, &text, speller);
+        spelling_spans(&chars, &misspelled_chars(&text, misspelled))
+    }
+}
+
+// Marca, caractere a caractere, as palavras desconhecidas do texto
+fn misspelled_chars(text: &str, ranges: &[Range<usize>]) -> Vec<bool> {
+    text.char_indices()
+        .map(|(index, _)| ranges.iter().any(|range| range.contains(&index)))
+        .collect()
+}
+
+// Sublinha em vermelho os trechos marcados
+fn spelling_spans(chars: &[char], marks: &[bool]) -> Spans<'static> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    for index in 1..=chars.len() {
+        if index == chars.len() || marks[index] != marks[start] {
+            let text: String = chars[start..index].iter().collect();
+            spans.push(if marks[start] {
+                Span::styled(
+                    text,
+                    Style::default().fg(Color::Red).add_modifier(Modifier::UNDERLINED),
+                )
+            } else {
+                Span::raw(text)
+            });
+            start = index;
+        }
+    }
+    Spans::from(spans)
+}
+
+pub fn status_style(status: EntryStatus) -> Style {
+    match status {
+        EntryStatus::Translated => Style::default().fg(Color::Green),
+        EntryStatus::NeedsReview => Style::default().fg(Color::LightMagenta),
+        EntryStatus::Draft => Style::default().fg(Color::LightBlue),
+        EntryStatus::Untranslated => Style::default(),
+    }
+}
+
 pub fn format_json_value(value: &serde_json::Value) -> String {
+    // Entrada sem tradução
+    if value.is_null() {
+        return String::new();
+    }
     value.to_string().replace('"', "")
 }
@@ -0,0 +1,174 @@
+use regex::Regex;
+use serde_json::Value;
+use std::sync::LazyLock;
+
+use crate::app::{Entry, EntryStatus};
+use crate::glossary::Glossary;
+use crate::localization::Locale;
+
+// Placeholders comuns: {nome}, {{nome}}, ${nome}, %s, %1$d
+pub static PLACEHOLDER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{\{\s*[\w.]+\s*\}\}|\$?\{[\w.]+\}|%(\d+\$)?[-+ 0#]*\d*(\.\d+)?[sdifuxXeEgGcp@]")
+        .unwrap()
+});
+
+pub static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"</?[A-Za-z][^<>]*>").unwrap());
+
+pub enum Issue {
+    MissingPlaceholder(String),
+    UnexpectedPlaceholder(String),
+    TagMismatch,
+    EmptyTranslation,
+    WhitespaceMismatch,
+    // Tradução maior que o limite de tamanho da chave
+    TooLong { length: usize, max: usize },
+    // Termo do glossário sem nenhuma das traduções exigidas
+    MissingTerm { term: String, expected: String },
+    ForbiddenTerm(String),
+}
+
+impl Issue {
+    pub fn describe(&self, locale: &Locale) -> String {
+        match self {
+            Issue::MissingPlaceholder(token) => {
+                locale.get_with_params("issue_missing_placeholder", &[("token", token)])
+            }
+            Issue::UnexpectedPlaceholder(token) => {
+                locale.get_with_params("issue_unexpected_placeholder", &[("token", token)])
+            }
+            Issue::TagMismatch => locale.get("issue_tag_mismatch").to_string(),
+            Issue::EmptyTranslation => locale.get("issue_empty_translation").to_string(),
+            Issue::WhitespaceMismatch => locale.get("issue_whitespace").to_string(),
+            Issue::TooLong { length, max } => locale.get_with_params(
+                "issue_too_long",
+                &[("length", &length.to_string()), ("max", &max.to_string())],
+            ),
+            Issue::MissingTerm { term, expected } => locale.get_with_params(
+                "issue_missing_term",
+                &[("term", term), ("expected", expected)],
+            ),
+            Issue::ForbiddenTerm(term) => {
+                locale.get_with_params("issue_forbidden_term", &[("term", term)])
+            }
+        }
+    }
+}
+
+// Esqueleto da tradução: placeholders e tags do texto de origem na mesma ordem,
+// sem o texto traduzível. Trechos com espaço entre dois marcadores viram um
+// espaço, e os espaços das pontas do original são mantidos.
+pub fn skeleton(text: &str) -> String {
+    let mut tokens: Vec<(usize, usize)> = PLACEHOLDER
+        .find_iter(text)
+        .chain(TAG.find_iter(text))
+        .map(|m| (m.start(), m.end()))
+        .collect();
+    tokens.sort_unstable();
+
+    let trimmed = text.trim_start();
+    let mut result = text[..text.len() - trimmed.len()].to_string();
+    let mut position = None;
+    for (start, end) in tokens {
+        match position {
+            Some(previous) if start < previous => continue,
+            Some(previous) if text[previous..start].contains(char::is_whitespace) => {
+                result.push(' ');
+            }
+            _ => {}
+        }
+        result.push_str(&text[start..end]);
+        position = Some(end);
+    }
+    result.push_str(&text[text.trim_end().len()..]);
+    result
+}
+
+pub fn placeholders(text: &str) -> Vec<&str> {
+    let mut tokens: Vec<&str> = PLACEHOLDER.find_iter(text).map(|m| m.as_str()).collect();
+    tokens.sort_unstable();
+    tokens
+}
+
+fn tags(text: &str) -> Vec<&str> {
+    let mut tokens: Vec<&str> = TAG.find_iter(text).map(|m| m.as_str()).collect();
+    tokens.sort_unstable();
+    tokens
+}
+
+pub fn validate(entry: &Entry, glossary: &Glossary) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    // Entradas bloqueadas são gravadas com o texto de origem
+    if entry.is_locked() {
+        return issues;
+    }
+    let Value::String(original) = &entry.original else {
+        return issues;
+    };
+    let translated = match &entry.translated {
+        Value::String(translated) => translated.as_str(),
+        Value::Null => "",
+        _ => return issues,
+    };
+
+    if translated.trim().is_empty() {
+        if entry.is_translated() && !original.trim().is_empty() {
+            issues.push(Issue::EmptyTranslation);
+        }
+        return issues;
+    }
+
+    if let Some(max) = entry.max_length {
+        let length = translated.chars().count();
+        if length > max {
+            issues.push(Issue::TooLong { length, max });
+        }
+    }
+
+    let expected = placeholders(original);
+    let found = placeholders(translated);
+    for token in &expected {
+        if !found.contains(token) {
+            issues.push(Issue::MissingPlaceholder(token.to_string()));
+        }
+    }
+    for token in &found {
+        if !expected.contains(token) {
+            issues.push(Issue::UnexpectedPlaceholder(token.to_string()));
+        }
+    }
+
+    if tags(original) != tags(translated) {
+        issues.push(Issue::TagMismatch);
+    }
+
+    let edges = |s: &str| {
+        (
+            s.starts_with(char::is_whitespace),
+            s.ends_with(char::is_whitespace),
+        )
+    };
+    if edges(original) != edges(translated) {
+        issues.push(Issue::WhitespaceMismatch);
+    }
+
+    // Entradas ainda com o texto de origem não passam pelo glossário
+    if entry.status != EntryStatus::Untranslated || translated != original {
+        let (missing, forbidden) = glossary.check(original, translated);
+        issues.extend(missing.into_iter().map(|term| Issue::MissingTerm {
+            term: term.source.clone(),
+            expected: term.targets.join(" / "),
+        }));
+        issues.extend(
+            forbidden
+                .into_iter()
+                .map(|term| Issue::ForbiddenTerm(term.to_string())),
+        );
+    }
+
+    issues
+}
+
+pub fn has_issues(entry: &Entry, glossary: &Glossary) -> bool {
+    !validate(entry, glossary).is_empty()
+}
@@ -0,0 +1,4 @@
+# Main title
+title = Settings
+save = Save
+    .tooltip = Save the document
@@ -0,0 +1,4 @@
+# Main title
+title = Configurações
+save = Save
+    .tooltip = Salva o documento